@@ -1,16 +1,19 @@
 use drone_macros_core::unkeywordize;
 use inflector::Inflector;
 use proc_macro::TokenStream;
+use proc_macro2::Span;
 use quote::{format_ident, quote};
 use std::collections::HashSet;
 use syn::{
     braced,
     parse::{Parse, ParseStream, Result},
-    parse_macro_input, Attribute, Ident, LitInt, Token, Visibility,
+    parse_macro_input, Attribute, Error, Ident, Lit, LitInt, Meta, MetaNameValue, Token,
+    Visibility,
 };
 
 struct Reg {
     attrs: Vec<Attribute>,
+    endian: Option<Ident>,
     vis: Visibility,
     block: Ident,
     ident: Ident,
@@ -27,18 +30,24 @@ struct Field {
     offset: LitInt,
     width: LitInt,
     traits: Vec<Ident>,
+    enum_ty: Option<Ident>,
 }
 
 impl Parse for Reg {
     fn parse(input: ParseStream<'_>) -> Result<Self> {
-        let attrs = input.call(Attribute::parse_outer)?;
+        let mut attrs = input.call(Attribute::parse_outer)?;
+        let endian = take_endian(&mut attrs)?;
         let vis = input.parse()?;
         input.parse::<Token![mod]>()?;
         let block = input.parse()?;
         let ident = input.parse()?;
         input.parse::<Token![;]>()?;
         let address = input.parse()?;
-        let size = input.parse::<LitInt>()?.base10_parse()?;
+        let size_lit = input.parse::<LitInt>()?;
+        let size = size_lit.base10_parse()?;
+        if ![8, 16, 32, 64].contains(&size) {
+            return Err(Error::new(size_lit.span(), "register size must be 8, 16, 32, or 64 bits"));
+        }
         let reset = input.parse()?;
         let mut traits = Vec::new();
         while !input.peek(Token![;]) {
@@ -49,7 +58,7 @@ impl Parse for Reg {
         while !input.is_empty() {
             fields.push(input.parse()?);
         }
-        Ok(Self { attrs, vis, block, ident, address, size, reset, traits, fields })
+        Ok(Self { attrs, endian, vis, block, ident, address, size, reset, traits, fields })
     }
 }
 
@@ -65,30 +74,161 @@ impl Parse for Field {
         while !content.is_empty() {
             traits.push(content.parse()?);
         }
-        Ok(Self { attrs, ident, offset, width, traits })
+        let enum_ty = if input.peek(Token![as]) {
+            input.parse::<Token![as]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        Ok(Self { attrs, ident, offset, width, traits, enum_ty })
     }
 }
 
 #[allow(clippy::too_many_lines, clippy::cognitive_complexity)]
 pub fn proc_macro(input: TokenStream) -> TokenStream {
-    let Reg { attrs, vis, block, ident, address, size, reset, traits, fields } =
+    let Reg { attrs, endian, vis, block, ident, address, size, reset, traits, fields } =
         parse_macro_input!(input as Reg);
     let t = format_ident!("_T");
 
     let attrs = &attrs;
     let val_ty = format_ident!("u{}", size);
+    let atomic = traits.iter().any(|name| name == "AtomicReg");
+    let access_tag = if atomic {
+        quote!(::drone_core::reg::tag::RegAtomic)
+    } else {
+        quote!(::drone_core::reg::tag::RegTag)
+    };
+    // Bit-band aliases address physical bits, so they are generated only for
+    // registers within the bit-band regions, and with matching bit numbering.
+    let bitband = match address.base10_parse::<u64>() {
+        Ok(address) => {
+            bitband_region(address, size) && endian.as_ref().map_or(true, |endian| endian != "be")
+        }
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let endian_tokens = endian.map(|endian| {
+        let from_mem = format_ident!("from_{}", endian);
+        let to_mem = format_ident!("to_{}", endian);
+        quote! {
+            #[inline]
+            fn bits_from_mem(bits: #val_ty) -> #val_ty {
+                #val_ty::#from_mem(bits)
+            }
+
+            #[inline]
+            fn bits_to_mem(bits: #val_ty) -> #val_ty {
+                bits.#to_mem()
+            }
+        }
+    });
+    let readable = traits.iter().any(|name| name == "RReg");
     let mut imports = traits.iter().cloned().collect::<HashSet<_>>();
     let mut tokens = Vec::new();
     let mut struct_tokens = Vec::new();
     let mut ctor_tokens = Vec::new();
-    for Field { attrs, ident, offset, width, traits } in &fields {
+    let mut debug_tokens = Vec::new();
+    let mut fields_struct_tokens = Vec::new();
+    let mut fields_ctor_tokens = Vec::new();
+    let mut declared = 0_u128;
+    let mut declared_read = 0_u128;
+    let mut declared_write = 0_u128;
+    for Field { attrs, ident, offset, width, traits, enum_ty } in &fields {
         let field_snk = ident.to_string().to_snake_case();
         let mut field_psc = ident.to_string().to_pascal_case();
-        if field_psc == "Val" {
+        if field_psc == "Val" || field_psc == "Fields" {
             field_psc.push('_');
         }
         let field_psc = format_ident!("{}", field_psc);
         let field_ident = format_ident!("{}", unkeywordize(&field_snk));
+        let mask = match (offset.base10_parse::<u32>(), width.base10_parse::<u32>()) {
+            (Ok(offset), Ok(width)) if offset.saturating_add(width) > u32::from(size) => {
+                return Error::new(ident.span(), "field exceeds the register size")
+                    .to_compile_error()
+                    .into();
+            }
+            (Ok(offset), Ok(width)) => {
+                let mask = 1_u128.checked_shl(width).map_or(u128::max_value(), |bit| bit - 1);
+                mask.checked_shl(offset).unwrap_or(0)
+            }
+            (Err(err), _) | (_, Err(err)) => return err.to_compile_error().into(),
+        };
+        if traits.iter().any(|name| name == "RRRegField") {
+            if declared_read & mask != 0 {
+                return Error::new(ident.span(), "field overlaps another readable field")
+                    .to_compile_error()
+                    .into();
+            }
+            declared_read |= mask;
+        }
+        if traits.iter().any(|name| name == "WWRegField" || name == "W1cRegField") {
+            if declared_write & mask != 0 {
+                return Error::new(ident.span(), "field overlaps another writable field")
+                    .to_compile_error()
+                    .into();
+            }
+            declared_write |= mask;
+        }
+        declared |= mask;
+        let mask_const = format_ident!("{}_MASK", ident.to_string().to_screaming_snake_case());
+        let offset_const = format_ident!("{}_OFFSET", ident.to_string().to_screaming_snake_case());
+        let mask = LitInt::new(&mask.to_string(), Span::call_site());
+        let docs = attrs.iter().filter(|attr| attr.path.is_ident("doc")).collect::<Vec<_>>();
+        let docs_sep = if docs.is_empty() { None } else { Some(quote!(#[doc = ""])) };
+        tokens.push(quote! {
+            /// The bit mask of the field.
+            #docs_sep
+            #(#docs)*
+            pub const #mask_const: #val_ty = #mask;
+
+            /// The bit offset of the field.
+            #docs_sep
+            #(#docs)*
+            pub const #offset_const: u32 = #offset;
+        });
+        debug_tokens.push(quote! {
+            debug.field(stringify!(#ident), &((self.0 & #mask_const) >> #offset_const));
+        });
+        if width.base10_digits() == "1" {
+            fields_struct_tokens.push(quote! {
+                #(#docs)*
+                pub #field_ident: bool
+            });
+            fields_ctor_tokens.push(quote! {
+                #field_ident: (self.0 & #mask_const) != 0
+            });
+        } else {
+            fields_struct_tokens.push(quote! {
+                #(#docs)*
+                pub #field_ident: #val_ty
+            });
+            fields_ctor_tokens.push(quote! {
+                #field_ident: (self.0 & #mask_const) >> #offset_const
+            });
+        }
+        if let Some(rr) = traits.iter().find(|name| *name == "RRRegField") {
+            if !readable {
+                return Error::new(rr.span(), "`RRRegField` requires the register to be `RReg`")
+                    .to_compile_error()
+                    .into();
+            }
+        }
+        if let Some(w1c) = traits.iter().find(|name| *name == "W1cRegField") {
+            if traits.iter().any(|name| name == "WWRegField") {
+                return Error::new(w1c.span(), "`W1cRegField` can't be combined with `WWRegField`")
+                    .to_compile_error()
+                    .into();
+            }
+            let clear_field = format_ident!("clear_{}", field_snk);
+            tokens.push(quote! {
+                impl<#t: #access_tag> Reg<#t> {
+                    #(#attrs)*
+                    #[inline]
+                    pub fn #clear_field(&self) {
+                        ::drone_core::reg::field::W1cRegField::store_clear(&self.#field_ident);
+                    }
+                }
+            });
+        }
         imports.extend(traits.iter().cloned());
         struct_tokens.push(quote! {
             #(#attrs)*
@@ -127,10 +267,15 @@ pub fn proc_macro(input: TokenStream) -> TokenStream {
         });
         for ident in traits {
             tokens.push(quote! {
-                impl<#t: ::drone_core::reg::tag::RegTag> #ident<#t> for #field_psc<#t> {}
+                impl<#t: #access_tag> #ident<#t> for #field_psc<#t> {}
             });
         }
         if width.base10_digits() == "1" {
+            if let Some(enum_ty) = enum_ty {
+                return Error::new(enum_ty.span(), "single-bit fields can't be mapped to a type")
+                    .to_compile_error()
+                    .into();
+            }
             tokens.push(quote! {
                 impl<#t> ::drone_core::reg::field::RegFieldBit<#t> for #field_psc<#t>
                 where
@@ -140,7 +285,7 @@ pub fn proc_macro(input: TokenStream) -> TokenStream {
             });
             if traits.iter().any(|name| name == "RRRegField") {
                 tokens.push(quote! {
-                    impl<'a, #t: ::drone_core::reg::tag::RegTag> Hold<'a, #t> {
+                    impl<'a, #t: #access_tag> Hold<'a, #t> {
                         #(#attrs)*
                         #[inline]
                         pub fn #field_ident(&self) -> bool {
@@ -157,7 +302,7 @@ pub fn proc_macro(input: TokenStream) -> TokenStream {
                 let clear_field = format_ident!("clear_{}", field_snk);
                 let toggle_field = format_ident!("toggle_{}", field_snk);
                 tokens.push(quote! {
-                    impl<'a, #t: ::drone_core::reg::tag::RegTag> Hold<'a, #t> {
+                    impl<'a, #t: #access_tag> Hold<'a, #t> {
                         #(#attrs)*
                         #[inline]
                         pub fn #set_field(&mut self) -> &mut Self {
@@ -189,6 +334,88 @@ pub fn proc_macro(input: TokenStream) -> TokenStream {
                         }
                     }
                 });
+                if bitband {
+                    let set_field_bitband = format_ident!("set_{}_bitband", field_snk);
+                    let clear_field_bitband = format_ident!("clear_{}_bitband", field_snk);
+                    tokens.push(quote! {
+                        #[cfg(all(
+                            target_arch = "arm",
+                            target_feature = "mclass",
+                            target_feature = "v7",
+                        ))]
+                        impl<#t: #access_tag> Reg<#t> {
+                            #(#attrs)*
+                            #[inline]
+                            pub fn #set_field_bitband(&self) {
+                                unsafe {
+                                    ::core::ptr::write_volatile(
+                                        ::drone_core::reg::bitband_alias(#address, #offset)
+                                            as *mut u32,
+                                        1,
+                                    );
+                                }
+                            }
+
+                            #(#attrs)*
+                            #[inline]
+                            pub fn #clear_field_bitband(&self) {
+                                unsafe {
+                                    ::core::ptr::write_volatile(
+                                        ::drone_core::reg::bitband_alias(#address, #offset)
+                                            as *mut u32,
+                                        0,
+                                    );
+                                }
+                            }
+                        }
+                    });
+                }
+                if traits.iter().any(|name| name == "RRRegField") {
+                    // Atomic integers wider than 32 bits are not available on
+                    // all targets.
+                    if size <= 32 {
+                        let test_and_set_field = format_ident!("test_and_set_{}", field_snk);
+                        tokens.push(quote! {
+                            impl<#t: ::drone_core::reg::tag::RegAtomic> Reg<#t> {
+                                #(#attrs)*
+                                #[inline]
+                                pub fn #test_and_set_field(&self) -> bool {
+                                    ::drone_core::reg::RwRegAtomicBit::test_and_set_bit(
+                                        self, #offset,
+                                    )
+                                }
+                            }
+                        });
+                        // Separate impls for each atomic tag, because a
+                        // generic one would overlap with the `Urt` impl below.
+                        for tag in &[format_ident!("Srt"), format_ident!("Crt")] {
+                            tokens.push(quote! {
+                                impl Reg<::drone_core::reg::tag::#tag> {
+                                    #(#attrs)*
+                                    #[inline]
+                                    pub fn #toggle_field(&self) {
+                                        ::drone_core::reg::RwRegAtomicBit::toggle_bit(
+                                            self, #offset,
+                                        );
+                                    }
+                                }
+                            });
+                        }
+                    }
+                    if !atomic {
+                        tokens.push(quote! {
+                            impl Reg<::drone_core::reg::tag::Urt> {
+                                #(#attrs)*
+                                #[inline]
+                                pub fn #toggle_field(&mut self) {
+                                    ::drone_core::reg::RwRegUnsync::modify(self, |r| {
+                                        r.#toggle_field()
+                                    });
+                                }
+                            }
+                        });
+                    }
+                }
             }
         } else {
             tokens.push(quote! {
@@ -200,7 +427,7 @@ pub fn proc_macro(input: TokenStream) -> TokenStream {
             });
             if traits.iter().any(|name| name == "RRRegField") {
                 tokens.push(quote! {
-                    impl<'a, #t: ::drone_core::reg::tag::RegTag> Hold<'a, #t> {
+                    impl<'a, #t: #access_tag> Hold<'a, #t> {
                         #(#attrs)*
                         #[inline]
                         pub fn #field_ident(&self) -> #val_ty {
@@ -212,17 +439,49 @@ pub fn proc_macro(input: TokenStream) -> TokenStream {
                     }
                 });
             }
+            if let Some(enum_ty) = enum_ty {
+                imports.insert(enum_ty.clone());
+                if traits.iter().any(|name| name == "RRRegField") {
+                    let read_field = format_ident!("read_{}", field_snk);
+                    tokens.push(quote! {
+                        impl<'a, #t: #access_tag> Hold<'a, #t> {
+                            #(#attrs)*
+                            #[inline]
+                            pub fn #read_field(&self) -> #enum_ty {
+                                let bits = ::drone_core::reg::field::RRRegFieldBits::read(
+                                    &self.reg.#field_ident,
+                                    &self.val,
+                                );
+                                <#enum_ty as ::drone_core::reg::field::RegFieldEnum<#val_ty>>
+                                    ::from_bits(bits)
+                                    .unwrap_or_else(|| {
+                                        <#enum_ty as ::drone_core::reg::field::RegFieldEnum<
+                                            #val_ty,
+                                        >>::fallback(bits)
+                                    })
+                            }
+                        }
+                    });
+                }
+            }
             if traits.iter().any(|name| name == "WWRegField") {
                 let write_field = format_ident!("write_{}", field_snk);
+                let (write_arg, write_bits) = match enum_ty {
+                    Some(enum_ty) => (
+                        quote!(value: #enum_ty),
+                        quote!(::drone_core::reg::field::RegFieldEnum::<#val_ty>::into_bits(value)),
+                    ),
+                    None => (quote!(bits: #val_ty), quote!(bits)),
+                };
                 tokens.push(quote! {
-                    impl<'a, #t: ::drone_core::reg::tag::RegTag> Hold<'a, #t> {
+                    impl<'a, #t: #access_tag> Hold<'a, #t> {
                         #(#attrs)*
                         #[inline]
-                        pub fn #write_field(&mut self, bits: #val_ty) -> &mut Self {
+                        pub fn #write_field(&mut self, #write_arg) -> &mut Self {
                             ::drone_core::reg::field::WWRegFieldBits::write(
                                 &self.reg.#field_ident,
                                 &mut self.val,
-                                bits,
+                                #write_bits,
                             );
                             self
                         }
@@ -237,9 +496,35 @@ pub fn proc_macro(input: TokenStream) -> TokenStream {
     }
     for ident in traits {
         tokens.push(quote! {
-            impl<#t: ::drone_core::reg::tag::RegTag> #ident<#t> for Reg<#t> {}
+            impl<#t: #access_tag> #ident<#t> for Reg<#t> {}
+        });
+    }
+    let full = if size >= 128 { u128::max_value() } else { (1 << size) - 1 };
+    let reserved = !declared & full;
+    if reserved != 0 {
+        let mask = LitInt::new(&reserved.to_string(), Span::call_site());
+        debug_tokens.push(quote! {
+            let reserved = self.0 & #mask;
+            if reserved != 0 {
+                debug.field("reserved", &format_args!("{:#x}", reserved));
+            }
         });
     }
+    let val_from_bits = if reserved == 0 {
+        quote! {
+            /// Creates a new value from raw `bits`.
+            ///
+            /// The fields cover all bits of the register, so any `bits` are
+            /// accepted.
+            #[inline]
+            pub fn val_from_bits(&self, bits: #val_ty) -> Val {
+                Val(bits)
+            }
+        }
+    } else {
+        quote!()
+    };
+    let reserved = LitInt::new(&reserved.to_string(), Span::call_site());
     let reg_full = format_ident!(
         "{}_{}",
         block.to_string().to_snake_case(),
@@ -262,6 +547,123 @@ pub fn proc_macro(input: TokenStream) -> TokenStream {
             #[derive(Bitfield, Clone, Copy)]
             pub struct Val(#val_ty);
 
+            impl Val {
+                /// Creates a new value from raw `bits` without any checks.
+                ///
+                /// This is the inverse of [`Bitfield::bits`], and the round
+                /// trip is lossless, including the reserved bits.
+                ///
+                /// # Safety
+                ///
+                /// This function is unsafe because it doesn't require a token.
+                #[inline]
+                pub unsafe fn from_raw(bits: #val_ty) -> Self {
+                    Self(bits)
+                }
+
+                /// Extracts the values of all fields at once.
+                #[inline]
+                pub fn fields(&self) -> Fields {
+                    Fields { #(#fields_ctor_tokens,)* }
+                }
+            }
+
+            /// The values of all fields of the register.
+            #[derive(Clone, Copy, Debug)]
+            pub struct Fields {
+                #(#fields_struct_tokens),*
+            }
+
+            impl ::core::fmt::Debug for Val {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    let mut debug = f.debug_struct(stringify!(#ident));
+                    #(#debug_tokens)*
+                    debug.finish()
+                }
+            }
+
+            impl ::core::ops::BitOr for Val {
+                type Output = Self;
+
+                #[inline]
+                fn bitor(self, rhs: Self) -> Self {
+                    Self(self.0 | rhs.0)
+                }
+            }
+
+            impl ::core::ops::BitAnd for Val {
+                type Output = Self;
+
+                #[inline]
+                fn bitand(self, rhs: Self) -> Self {
+                    Self(self.0 & rhs.0)
+                }
+            }
+
+            impl ::core::ops::BitXor for Val {
+                type Output = Self;
+
+                #[inline]
+                fn bitxor(self, rhs: Self) -> Self {
+                    Self(self.0 ^ rhs.0)
+                }
+            }
+
+            impl ::core::ops::Not for Val {
+                type Output = Self;
+
+                #[inline]
+                fn not(self) -> Self {
+                    Self(!self.0)
+                }
+            }
+
+            impl ::core::convert::From<Val> for #val_ty {
+                #[inline]
+                fn from(val: Val) -> Self {
+                    val.0
+                }
+            }
+
+            impl<#t: ::drone_core::reg::tag::RegTag> Reg<#t> {
+                /// Creates a new value from raw `bits`, checking that no
+                /// reserved bits are set.
+                ///
+                /// # Errors
+                ///
+                /// Returns an error if any of the bits not covered by the
+                /// register fields are set.
+                #[inline]
+                pub fn from_bits_checked(
+                    &self,
+                    bits: #val_ty,
+                ) -> ::core::result::Result<Val, ::drone_core::reg::ReservedBitsError<#val_ty>> {
+                    let reserved = bits & #reserved;
+                    if reserved == 0 {
+                        Ok(Val(bits))
+                    } else {
+                        Err(::drone_core::reg::ReservedBitsError::new(reserved))
+                    }
+                }
+
+                /// Creates a new value from raw `bits`, checking that no
+                /// reserved bits are set.
+                ///
+                /// # Errors
+                ///
+                /// Returns an error if any of the bits not covered by the
+                /// register fields are set.
+                #[inline]
+                pub fn try_val_from_bits(
+                    &self,
+                    bits: #val_ty,
+                ) -> ::core::result::Result<Val, ::drone_core::reg::ReservedBitsError<#val_ty>> {
+                    self.from_bits_checked(bits)
+                }
+
+                #val_from_bits
+            }
+
             #(#attrs)*
             #[derive(Clone, Copy)]
             pub struct Reg<#t: ::drone_core::reg::tag::RegTag> {
@@ -288,6 +690,8 @@ pub fn proc_macro(input: TokenStream) -> TokenStream {
                 unsafe fn val_from(bits: #val_ty) -> Val {
                     Val(bits)
                 }
+
+                #endian_tokens
             }
 
             impl<'a, #t> ::drone_core::reg::RegRef<'a, #t> for Reg<#t>
@@ -328,3 +732,32 @@ pub fn proc_macro(input: TokenStream) -> TokenStream {
     };
     expanded.into()
 }
+
+// Checks that the register lies within the first megabyte of the SRAM or the
+// peripheral region, which is mapped to the bit-band alias region.
+fn bitband_region(address: u64, size: u8) -> bool {
+    let end = address + u64::from(size / 8);
+    [0x2000_0000, 0x4000_0000].iter().any(|&base| address >= base && end <= base + 0x0010_0000)
+}
+
+fn take_endian(attrs: &mut Vec<Attribute>) -> Result<Option<Ident>> {
+    let mut endian = None;
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("endian")) {
+        match attr.parse_meta()? {
+            Meta::NameValue(MetaNameValue { lit: Lit::Str(ref lit), .. })
+                if lit.value() == "big" || lit.value() == "little" =>
+            {
+                let short = if lit.value() == "big" { "be" } else { "le" };
+                endian = Some(Ident::new(short, lit.span()));
+            }
+            meta => {
+                return Err(Error::new_spanned(
+                    meta,
+                    "expected `#[endian = \"big\"]` or `#[endian = \"little\"]`",
+                ));
+            }
+        }
+    }
+    attrs.retain(|attr| !attr.path.is_ident("endian"));
+    Ok(endian)
+}
@@ -122,6 +122,8 @@ pub fn proc_macro(input: TokenStream) -> TokenStream {
     let mut tokens = Vec::new();
     let mut def_tokens = Vec::new();
     let mut ctor_tokens = Vec::new();
+    let mut field_tokens = Vec::new();
+    let mut ty_tokens = Vec::new();
     for Block { attrs, vis, ident, regs } in blocks {
         let block_snk = ident.to_string().to_snake_case();
         let block_ident = format_ident!("{}", unkeywordize(&block_snk));
@@ -132,6 +134,9 @@ pub fn proc_macro(input: TokenStream) -> TokenStream {
             let reg_long = format_ident!("{}_{}", block_snk, reg_snk);
             let reg_short = format_ident!("{}", unkeywordize(&reg_snk));
             let macro_root_path = macro_root_path.iter();
+            let reg_ty = quote! {
+                $crate#(#macro_root_path)*::#block_ident::#reg_psc<::drone_core::reg::tag::Srt>
+            };
             block_tokens.push(quote! {
                 pub use #root_path::#reg_long as #reg_short;
                 pub use #root_path::#reg_long::Reg as #reg_psc;
@@ -139,13 +144,13 @@ pub fn proc_macro(input: TokenStream) -> TokenStream {
             def_tokens.push(quote! {
                 #(#attrs)*
                 #[allow(missing_docs)]
-                pub #reg_long: $crate#(#macro_root_path)*::#block_ident::#reg_psc<
-                    ::drone_core::reg::tag::Srt,
-                >,
+                pub #reg_long: #reg_ty,
             });
             ctor_tokens.push(quote! {
                 #reg_long: ::drone_core::token::Token::take(),
             });
+            field_tokens.push(quote!(#reg_long,));
+            ty_tokens.push(quote!(#reg_ty,));
         }
         tokens.push(quote! {
             #(#attrs)*
@@ -162,6 +167,8 @@ pub fn proc_macro(input: TokenStream) -> TokenStream {
                 $(#[$attr])* $vis struct $ty;
                 { #(#def_tokens)* $($def)* }
                 { #(#ctor_tokens)* $($ctor)* }
+                { #(#field_tokens)* $($field)* }
+                { #(#ty_tokens)* $($field_ty)* }
             }
         },
         None => quote! {
@@ -174,6 +181,51 @@ pub fn proc_macro(input: TokenStream) -> TokenStream {
                     Self { #(#ctor_tokens)* $($ctor)* }
                 }
             }
+            static __REG_TOKENS_TAKEN: ::core::sync::atomic::AtomicBool =
+                ::core::sync::atomic::AtomicBool::new(false);
+            unsafe impl ::drone_core::token::CheckedToken for $ty {
+                #[inline]
+                fn try_take() -> ::core::result::Result<Self, ::drone_core::token::AlreadyTakenError> {
+                    if __REG_TOKENS_TAKEN.swap(true, ::core::sync::atomic::Ordering::Acquire) {
+                        Err(::drone_core::token::AlreadyTakenError)
+                    } else {
+                        Ok(unsafe { <Self as ::drone_core::token::Token>::take() })
+                    }
+                }
+
+                #[inline]
+                fn release(self) {
+                    __REG_TOKENS_TAKEN.store(false, ::core::sync::atomic::Ordering::Release);
+                }
+            }
+            impl $ty {
+                /// Takes all register tokens at once.
+                ///
+                /// Unlike `Token::take`, this function is safe, because it
+                /// checks a run-time flag to ensure that only one instance of
+                /// the index exists. The instances created with `Token::take`
+                /// are not tracked, so the index should be acquired either
+                /// only with this function, or only with `Token::take`.
+                ///
+                /// The flag is cleared only by `CheckedToken::release`.
+                /// Dropping or splitting the index keeps it taken forever.
+                ///
+                /// # Errors
+                ///
+                /// If the index has already been taken.
+                #[inline]
+                $vis fn take_all(
+                ) -> ::core::result::Result<Self, ::drone_core::token::AlreadyTakenError> {
+                    <Self as ::drone_core::token::CheckedToken>::try_take()
+                }
+
+                /// Splits the index into a tuple of register tokens.
+                #[inline]
+                $vis fn split(self) -> ( #(#ty_tokens)* $($field_ty)* ) {
+                    let Self { #(#field_tokens)* $($field)* } = self;
+                    ( #(#field_tokens)* $($field)* )
+                }
+            }
             #[no_mangle]
             fn __reg_tokens_singularity_check() {}
         },
@@ -187,13 +239,15 @@ pub fn proc_macro(input: TokenStream) -> TokenStream {
             ) => {
                 #next_macro! {
                     $(#[$attr])* $vis struct $ty;
-                    {} {}
+                    {} {} {} {}
                 }
             };
             (
                 $(#[$attr:meta])* $vis:vis struct $ty:ident;
                 { $($def:tt)* }
                 { $($ctor:tt)* }
+                { $($field:tt)* }
+                { $($field_ty:tt)* }
             ) => {
                 #macro_tokens
             };
@@ -43,6 +43,25 @@ pub fn proc_macro(input: TokenStream) -> TokenStream {
                     }
                 }
             }
+
+            static TAKEN: ::core::sync::atomic::AtomicBool =
+                ::core::sync::atomic::AtomicBool::new(false);
+
+            unsafe impl ::drone_core::token::CheckedToken for #ident {
+                #[inline]
+                fn try_take() -> ::core::result::Result<Self, ::drone_core::token::AlreadyTakenError> {
+                    if TAKEN.swap(true, ::core::sync::atomic::Ordering::Acquire) {
+                        Err(::drone_core::token::AlreadyTakenError)
+                    } else {
+                        Ok(unsafe { <Self as ::drone_core::token::Token>::take() })
+                    }
+                }
+
+                #[inline]
+                fn release(self) {
+                    TAKEN.store(false, ::core::sync::atomic::Ordering::Release);
+                }
+            }
         }
 
         #vis use #wrapper::#ident;
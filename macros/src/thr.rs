@@ -1,14 +1,18 @@
 use proc_macro::TokenStream;
-use quote::{format_ident, quote};
+use quote::{format_ident, quote, quote_spanned};
 use syn::{
     braced,
     parse::{Parse, ParseStream, Result},
-    parse_macro_input, Attribute, Expr, ExprPath, Ident, Token, Type, Visibility,
+    parse_macro_input, parse_quote, Attribute, Error, Expr, ExprPath, Ident, Meta, NestedMeta,
+    Token, Type, TypePath, Visibility,
 };
 
 struct Thr {
     array: ExprPath,
+    priority: Option<(Type, Expr)>,
+    swi: Option<(Type, Expr)>,
     thr_attrs: Vec<Attribute>,
+    thr_debug: bool,
     thr_vis: Visibility,
     thr_ident: Ident,
     thr_fields: Vec<Field>,
@@ -31,7 +35,29 @@ impl Parse for Thr {
         input.parse::<Token![use]>()?;
         let array = input.parse()?;
         input.parse::<Token![;]>()?;
-        let thr_attrs = input.call(Attribute::parse_outer)?;
+        let mut priority = None;
+        let mut swi = None;
+        while input.peek(Ident) && input.peek2(Token![:]) {
+            let ident = input.parse::<Ident>()?;
+            let slot = if ident == "priority" {
+                &mut priority
+            } else if ident == "swi" {
+                &mut swi
+            } else {
+                return Err(Error::new(ident.span(), "expected `priority` or `swi`"));
+            };
+            if slot.is_some() {
+                return Err(Error::new(ident.span(), "duplicate thread option"));
+            }
+            input.parse::<Token![:]>()?;
+            let ty = input.parse()?;
+            input.parse::<Token![=]>()?;
+            let init = input.parse()?;
+            input.parse::<Token![;]>()?;
+            *slot = Some((ty, init));
+        }
+        let mut thr_attrs = input.call(Attribute::parse_outer)?;
+        let thr_debug = take_derive_debug(&mut thr_attrs)?;
         let thr_vis = input.parse()?;
         input.parse::<Token![struct]>()?;
         let thr_ident = input.parse()?;
@@ -53,7 +79,10 @@ impl Parse for Thr {
         }
         Ok(Self {
             array,
+            priority,
+            swi,
             thr_attrs,
+            thr_debug,
             thr_vis,
             thr_ident,
             thr_fields,
@@ -82,7 +111,10 @@ impl Parse for Field {
 pub fn proc_macro(input: TokenStream) -> TokenStream {
     let Thr {
         array,
+        priority,
+        swi,
         thr_attrs,
+        thr_debug,
         thr_vis,
         thr_ident,
         thr_fields,
@@ -92,20 +124,138 @@ pub fn proc_macro(input: TokenStream) -> TokenStream {
         local_fields,
     } = parse_macro_input!(input as Thr);
     let local = format_ident!("Local");
+    let mut errors = Vec::new();
+    let priority = priority.map(|(ty, init)| {
+        if !is_u8(&ty) {
+            errors.push(Error::new_spanned(ty, "expected `u8`").to_compile_error());
+        }
+        quote!(const PRIORITY: u8 = #init;)
+    });
+    let (swi, pend) = swi.map_or_else(
+        || (quote!(), quote!()),
+        |(ty, init)| {
+            if !is_u8(&ty) {
+                errors.push(Error::new_spanned(ty, "expected `u8`").to_compile_error());
+            }
+            let swi = quote! {
+                /// The software interrupt index of the first thread. The thread
+                /// at position `i` within the threads array uses `SWI + i`.
+                pub const SWI: u8 = #init;
+            };
+            let pend = quote! {
+                impl ::drone_core::thr::Pend for #thr_ident {
+                    #[inline]
+                    fn pend(&self) {
+                        let index = (self as *const Self as usize
+                            - <Self as ::drone_core::thr::Thread>::first() as usize)
+                            / ::core::mem::size_of::<Self>();
+                        ::drone_core::thr::pend_swi(Self::SWI + index as u8);
+                    }
+                }
+            };
+            (swi, pend)
+        },
+    );
     let mut thr_tokens = Vec::new();
     let mut thr_ctor_tokens = Vec::new();
+    let mut thr_debug_tokens = Vec::new();
     let mut local_tokens = Vec::new();
     let mut local_ctor_tokens = Vec::new();
-    for Field { attrs, vis, ident, ty, init } in thr_fields {
-        thr_tokens.push(quote!(#(#attrs)* #vis #ident: #ty));
-        thr_ctor_tokens.push(quote!(#ident: #init));
+    let mut thr_accessor_tokens = Vec::new();
+    for Field { mut attrs, vis, ident, ty, init } in thr_fields {
+        thr_debug_tokens
+            .push(quote_spanned!(ident.span()=> .field(stringify!(#ident), &self.#ident)));
+        let atomic = if take_atomic(&mut attrs) {
+            let atomic = atomic_type(&ty);
+            if atomic.is_none() {
+                errors.push(
+                    Error::new_spanned(&ty, "`#[atomic]` field must have an integer type")
+                        .to_compile_error(),
+                );
+            }
+            atomic
+        } else {
+            None
+        };
+        if let Some(atomic) = atomic {
+            let load = format_ident!("load_{}", ident);
+            let store = format_ident!("store_{}", ident);
+            let fetch_add = format_ident!("fetch_add_{}", ident);
+            thr_tokens.push(quote!(#(#attrs)* #vis #ident: ::core::sync::atomic::#atomic));
+            thr_ctor_tokens.push(quote!(#ident: ::core::sync::atomic::#atomic::new(#init)));
+            thr_accessor_tokens.push(quote! {
+                /// Loads the value of the atomic field.
+                #[inline]
+                #vis fn #load(&self, order: ::core::sync::atomic::Ordering) -> #ty {
+                    self.#ident.load(order)
+                }
+
+                /// Stores `val` into the atomic field.
+                #[inline]
+                #vis fn #store(&self, val: #ty, order: ::core::sync::atomic::Ordering) {
+                    self.#ident.store(val, order);
+                }
+
+                /// Adds `val` to the atomic field, returning the previous value.
+                #[inline]
+                #vis fn #fetch_add(&self, val: #ty, order: ::core::sync::atomic::Ordering) -> #ty {
+                    self.#ident.fetch_add(val, order)
+                }
+            });
+        } else {
+            thr_tokens.push(quote!(#(#attrs)* #vis #ident: #ty));
+            thr_ctor_tokens.push(quote!(#ident: #init));
+        }
     }
+    let mut local_accessor_tokens = Vec::new();
     for Field { attrs, vis, ident, ty, init } in local_fields {
-        local_tokens.push(quote!(#(#attrs)* #vis #ident: #ty));
-        local_ctor_tokens.push(quote!(#ident: #init));
+        if let Visibility::Inherited = vis {
+            let ident_mut = format_ident!("{}_mut", ident);
+            local_tokens.push(quote!(#(#attrs)* #ident: ::core::cell::UnsafeCell<#ty>));
+            local_ctor_tokens.push(quote!(#ident: ::core::cell::UnsafeCell::new(#init)));
+            local_accessor_tokens.push(quote! {
+                /// Returns a reference to the thread-local field.
+                #[inline]
+                pub fn #ident(&self) -> &#ty {
+                    unsafe { &*self.#ident.get() }
+                }
+
+                /// Returns a mutable reference to the thread-local field.
+                ///
+                /// # Safety
+                ///
+                /// Other references to the field must not be alive while the
+                /// returned reference is in use.
+                #[inline]
+                #[allow(clippy::mut_from_ref)]
+                pub unsafe fn #ident_mut(&self) -> &mut #ty {
+                    &mut *self.#ident.get()
+                }
+            });
+        } else {
+            local_tokens.push(quote!(#(#attrs)* #vis #ident: #ty));
+            local_ctor_tokens.push(quote!(#ident: #init));
+        }
     }
 
+    let thr_debug = if thr_debug {
+        quote! {
+            impl ::core::fmt::Debug for #thr_ident {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    f.debug_struct(stringify!(#thr_ident))
+                        .field("fib_chain_is_empty", &self.fib_chain.is_empty())
+                        #(#thr_debug_tokens)*
+                        .finish()
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
     let expanded = quote! {
+        #(#errors)*
+
         mod __thr {
             #(#thr_attrs)*
             pub struct #thr_ident {
@@ -124,6 +274,11 @@ pub fn proc_macro(input: TokenStream) -> TokenStream {
             struct #local(#local_ident);
 
             impl #thr_ident {
+                /// The name of the thread type.
+                pub const NAME: &'static str = stringify!(#thr_ident);
+
+                #swi
+
                 /// Creates a new thread object with given `index`.
                 pub const fn new(index: usize) -> Self {
                     Self {
@@ -136,11 +291,21 @@ pub fn proc_macro(input: TokenStream) -> TokenStream {
                         #(#thr_ctor_tokens,)*
                     }
                 }
+
+                #(#thr_accessor_tokens)*
+            }
+
+            #thr_debug
+
+            impl #local_ident {
+                #(#local_accessor_tokens)*
             }
 
             impl ::drone_core::thr::Thread for #thr_ident {
                 type Local = #local_ident;
 
+                #priority
+
                 #[inline]
                 fn first() -> *const Self {
                     unsafe { super::#array.as_ptr() }
@@ -157,6 +322,15 @@ pub fn proc_macro(input: TokenStream) -> TokenStream {
                 }
             }
 
+            #pend
+
+            impl ::drone_core::thr::ThreadArray for #thr_ident {
+                #[inline]
+                fn all() -> ::core::slice::Iter<'static, Self> {
+                    unsafe { super::#array.iter() }
+                }
+            }
+
             impl ::drone_core::thr::ThreadLocal for #local_ident {
                 #[inline]
                 fn task(&self) -> &::drone_core::thr::TaskCell {
@@ -177,3 +351,69 @@ pub fn proc_macro(input: TokenStream) -> TokenStream {
     };
     expanded.into()
 }
+
+fn take_derive_debug(attrs: &mut Vec<Attribute>) -> Result<bool> {
+    let mut debug = false;
+    for attr in attrs.iter_mut() {
+        if !attr.path.is_ident("derive") {
+            continue;
+        }
+        let mut list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            _ => continue,
+        };
+        let len = list.nested.len();
+        list.nested = list
+            .nested
+            .into_iter()
+            .filter(|nested| match nested {
+                NestedMeta::Meta(Meta::Path(path)) => !path.is_ident("Debug"),
+                _ => true,
+            })
+            .collect();
+        if list.nested.len() != len {
+            debug = true;
+            *attr = parse_quote!(#[#list]);
+        }
+    }
+    Ok(debug)
+}
+
+fn take_atomic(attrs: &mut Vec<Attribute>) -> bool {
+    let len = attrs.len();
+    attrs.retain(|attr| !attr.path.is_ident("atomic"));
+    attrs.len() != len
+}
+
+fn is_u8(ty: &Type) -> bool {
+    match ty {
+        Type::Path(TypePath { qself: None, path }) => path.is_ident("u8"),
+        _ => false,
+    }
+}
+
+fn atomic_type(ty: &Type) -> Option<Ident> {
+    let path = match ty {
+        Type::Path(TypePath { qself: None, path }) => path,
+        _ => return None,
+    };
+    if path.leading_colon.is_some() || path.segments.len() != 1 {
+        return None;
+    }
+    let segment = &path.segments[0];
+    if !segment.arguments.is_empty() {
+        return None;
+    }
+    let atomic = match segment.ident.to_string().as_str() {
+        "u8" => "AtomicU8",
+        "u16" => "AtomicU16",
+        "u32" => "AtomicU32",
+        "usize" => "AtomicUsize",
+        "i8" => "AtomicI8",
+        "i16" => "AtomicI16",
+        "i32" => "AtomicI32",
+        "isize" => "AtomicIsize",
+        _ => return None,
+    };
+    Some(format_ident!("{}", atomic))
+}
@@ -29,6 +29,10 @@ where
 
     /// Returns `true` if all bits of the value are cleared.
     fn is_zero(self) -> bool;
+
+    /// Returns the number of trailing zeros in the binary representation of
+    /// the value.
+    fn trailing_zeros(self) -> u32;
 }
 
 macro_rules! bits {
@@ -48,6 +52,11 @@ macro_rules! bits {
             fn is_zero(self) -> bool {
                 self == 0
             }
+
+            #[inline]
+            fn trailing_zeros(self) -> u32 {
+                self.trailing_zeros()
+            }
         }
     };
 }
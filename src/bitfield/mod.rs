@@ -141,6 +141,53 @@ pub trait Bitfield: Sized + Send + Sync + Clone + Copy + 'static {
             self.bits() & !(bit_mask(width) << offset) | (bits & bit_mask(width)) << offset
         };
     }
+
+    /// Sets the bits set in `mask`.
+    #[inline]
+    fn set_mask(&mut self, mask: Self::Bits) {
+        *self.bits_mut() = self.bits() | mask;
+    }
+
+    /// Clears the bits set in `mask`.
+    #[inline]
+    fn clear_mask(&mut self, mask: Self::Bits) {
+        *self.bits_mut() = self.bits() & !mask;
+    }
+
+    /// Toggles the bits set in `mask`.
+    #[inline]
+    fn toggle_mask(&mut self, mask: Self::Bits) {
+        *self.bits_mut() = self.bits() ^ mask;
+    }
+
+    /// Returns an iterator over the offsets of the set bits, from the least
+    /// significant bit to the most significant bit.
+    #[inline]
+    fn set_bits(&self) -> SetBits<Self::Bits> {
+        SetBits(self.bits())
+    }
+}
+
+/// An iterator over the offsets of the set bits of a [`Bitfield`].
+///
+/// This struct is created by the [`set_bits`](Bitfield::set_bits) method on
+/// [`Bitfield`].
+#[derive(Clone, Debug)]
+pub struct SetBits<T: Bits>(T);
+
+impl<T: Bits> Iterator for SetBits<T> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        if self.0.is_zero() {
+            None
+        } else {
+            let offset = self.0.trailing_zeros();
+            self.0 = self.0 & (self.0 - T::from_usize(1));
+            Some(offset as usize)
+        }
+    }
 }
 
 fn bit_at<T: Bits>(offset: T) -> T {
@@ -5,7 +5,10 @@ use alloc::{borrow::Cow, rc::Rc, sync::Arc};
 use core::{
     ascii,
     cmp::Ordering,
+    convert::{TryFrom, TryInto},
     fmt::{self, Write},
+    iter::FusedIterator,
+    mem::size_of,
     slice::{self, memchr},
     str,
 };
@@ -92,17 +95,84 @@ pub struct CStr {
 /// ```
 /// use drone_core::ffi::{CStr, FromBytesWithNulError};
 ///
-/// let _: FromBytesWithNulError = CStr::from_bytes_with_nul(b"f\0oo").unwrap_err();
+/// assert_eq!(
+///     CStr::from_bytes_with_nul(b"f\0oo").unwrap_err(),
+///     FromBytesWithNulError::InteriorNul { position: 1 },
+/// );
+/// assert_eq!(
+///     CStr::from_bytes_with_nul(b"foo").unwrap_err(),
+///     FromBytesWithNulError::NotNulTerminated,
+/// );
 /// ```
-#[derive(Clone, PartialEq, Eq, Debug)]
-pub struct FromBytesWithNulError {
-    kind: FromBytesWithNulErrorKind,
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FromBytesWithNulError {
+    /// Data provided contains an interior nul byte at `position`.
+    InteriorNul {
+        /// The position of the interior nul byte.
+        position: usize,
+    },
+    /// Data provided is not nul terminated.
+    NotNulTerminated,
 }
 
+/// An error indicating that no nul byte was present.
+///
+/// A slice used to create a [`CStr`] must contain a nul byte somewhere within
+/// the slice.
+///
+/// This error is created by the
+/// [`from_bytes_until_nul`](CStr::from_bytes_until_nul) method on [`CStr`].
+///
+/// # Examples
+///
+/// ```
+/// use drone_core::ffi::{CStr, MissingNulError};
+///
+/// let _: MissingNulError = CStr::from_bytes_until_nul(b"foo").unwrap_err();
+/// ```
 #[derive(Clone, PartialEq, Eq, Debug)]
-enum FromBytesWithNulErrorKind {
-    InteriorNul(usize),
-    NotNulTerminated,
+pub struct MissingNulError(());
+
+/// An error indicating that a field is too small to hold a C string.
+///
+/// This error is created by the [`copy_into_field`](CStr::copy_into_field)
+/// method on [`CStr`].
+///
+/// # Examples
+///
+/// ```
+/// use drone_core::ffi::{CStr, FieldTooSmallError};
+///
+/// let c_str = CStr::from_bytes_with_nul(b"foo\0").expect("CStr::from_bytes_with_nul failed");
+/// let err = c_str.copy_into_field(&mut [0; 3]).unwrap_err();
+/// assert_eq!(err, FieldTooSmallError { required: 4 });
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FieldTooSmallError {
+    /// The number of bytes required to hold the string with the nul
+    /// terminator.
+    pub required: usize,
+}
+
+/// An iterator over the escaped characters of a [`CStr`].
+///
+/// This struct is created by the [`escape_default`](CStr::escape_default)
+/// method on [`CStr`].
+#[derive(Clone, Debug)]
+pub struct EscapeDefault<'a> {
+    bytes: slice::Iter<'a, u8>,
+    buf: [u8; 4],
+    pos: usize,
+    len: usize,
+}
+
+/// An iterator over sub-slices of a [`CStr`] separated by a byte.
+///
+/// This struct is created by the [`split`](CStr::split) method on [`CStr`].
+#[derive(Clone, Debug)]
+pub struct Split<'a> {
+    rest: Option<&'a [u8]>,
+    byte: u8,
 }
 
 impl CStr {
@@ -180,20 +250,60 @@ impl CStr {
         let nul_pos = memchr::memchr(0, bytes);
         if let Some(nul_pos) = nul_pos {
             if nul_pos + 1 != bytes.len() {
-                return Err(FromBytesWithNulError::interior_nul(nul_pos));
+                return Err(FromBytesWithNulError::InteriorNul { position: nul_pos });
             }
             Ok(unsafe { Self::from_bytes_with_nul_unchecked(bytes) })
         } else {
-            Err(FromBytesWithNulError::not_nul_terminated())
+            Err(FromBytesWithNulError::NotNulTerminated)
         }
     }
 
+    /// Creates a C string wrapper from a byte slice with any number of nuls.
+    ///
+    /// This function will cast the provided `bytes` to a `CStr` wrapper up to
+    /// and including the first nul byte, and return it together with the rest
+    /// of the slice following the nul terminator. This is useful for iterating
+    /// over several C strings stored back-to-back in a single buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drone_core::ffi::CStr;
+    ///
+    /// let (cstr, rest) = CStr::from_bytes_until_nul(b"foo\0bar\0").unwrap();
+    /// assert_eq!(cstr.to_bytes(), b"foo");
+    /// assert_eq!(rest, b"bar\0");
+    /// ```
+    ///
+    /// Creating a `CStr` without a nul byte is an error:
+    ///
+    /// ```
+    /// use drone_core::ffi::CStr;
+    ///
+    /// let c_str = CStr::from_bytes_until_nul(b"foo");
+    /// assert!(c_str.is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `bytes` doesn't contain a nul
+    /// byte.
+    pub fn from_bytes_until_nul(bytes: &[u8]) -> Result<(&Self, &[u8]), MissingNulError> {
+        let nul_pos = memchr::memchr(0, bytes).ok_or(MissingNulError(()))?;
+        let (head, rest) = bytes.split_at(nul_pos + 1);
+        Ok((unsafe { Self::from_bytes_with_nul_unchecked(head) }, rest))
+    }
+
     /// Unsafely creates a C string wrapper from a byte slice.
     ///
     /// This function will cast the provided `bytes` to a `CStr` wrapper without
     /// performing any sanity checks. The provided slice **must** be
     /// nul-terminated and not contain any interior nul bytes.
     ///
+    /// The input is not checked even in debug builds, because this function is
+    /// usable in constant expressions. Use
+    /// [`CStr::from_bytes_with_nul`] when the input is not known to be valid.
+    ///
     /// # Examples
     ///
     /// ```
@@ -261,6 +371,27 @@ impl CStr {
         self.inner.as_ptr()
     }
 
+    /// Returns the length of `self`, not counting the nul terminator.
+    ///
+    /// This is equivalent to `self.to_bytes().len()`. The length is already
+    /// stored alongside the pointer, so this is an O(1) operation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drone_core::ffi::CStr;
+    ///
+    /// let c_str = CStr::from_bytes_with_nul(b"foo\0").expect("CStr::from_bytes_with_nul failed");
+    /// assert_eq!(c_str.count_bytes(), 3);
+    ///
+    /// let c_str = CStr::from_bytes_with_nul(b"\0").expect("CStr::from_bytes_with_nul failed");
+    /// assert_eq!(c_str.count_bytes(), 0);
+    /// ```
+    #[inline]
+    pub fn count_bytes(&self) -> usize {
+        self.inner.len() - 1
+    }
+
     /// Converts this C string to a byte slice.
     ///
     /// The returned slice will **not** contain the trailing nul terminator that
@@ -284,6 +415,30 @@ impl CStr {
         &bytes[..bytes.len() - 1]
     }
 
+    /// Returns a reference to the first `N` bytes of this C string as an array.
+    ///
+    /// Returns `None` if the string is shorter than `N` bytes, not counting
+    /// the trailing nul terminator. Arrays of up to 32 bytes are supported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drone_core::ffi::CStr;
+    ///
+    /// let c_str = CStr::from_bytes_with_nul(b"ELF1\0").expect("CStr::from_bytes_with_nul failed");
+    /// let magic: Option<&[u8; 3]> = c_str.as_array();
+    /// assert_eq!(magic, Some(b"ELF"));
+    /// let header: Option<&[u8; 5]> = c_str.as_array();
+    /// assert_eq!(header, None);
+    /// ```
+    #[inline]
+    pub fn as_array<'a, A>(&'a self) -> Option<&'a A>
+    where
+        &'a A: TryFrom<&'a [u8]>,
+    {
+        self.to_bytes().get(..size_of::<A>()).and_then(|bytes| bytes.try_into().ok())
+    }
+
     /// Converts this C string to a byte slice containing the trailing 0 byte.
     ///
     /// This function is the equivalent of [`CStr::to_bytes`] except that it
@@ -306,6 +461,170 @@ impl CStr {
         unsafe { &*(&self.inner as *const [c_char] as *const [u8]) }
     }
 
+    /// Returns the position of the first occurrence of `byte` in this C
+    /// string, not counting the nul terminator.
+    ///
+    /// Searching for `0` always returns `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drone_core::ffi::CStr;
+    ///
+    /// let c_str = CStr::from_bytes_with_nul(b"OK:1\0").expect("CStr::from_bytes_with_nul failed");
+    /// assert_eq!(c_str.find_byte(b':'), Some(2));
+    /// assert_eq!(c_str.find_byte(b'?'), None);
+    /// ```
+    #[inline]
+    pub fn find_byte(&self, byte: u8) -> Option<usize> {
+        memchr::memchr(byte, self.to_bytes())
+    }
+
+    /// Returns `true` if this C string contains `byte`, not counting the nul
+    /// terminator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drone_core::ffi::CStr;
+    ///
+    /// let c_str = CStr::from_bytes_with_nul(b"OK:1\0").expect("CStr::from_bytes_with_nul failed");
+    /// assert!(c_str.contains_byte(b':'));
+    /// assert!(!c_str.contains_byte(0));
+    /// ```
+    #[inline]
+    pub fn contains_byte(&self, byte: u8) -> bool {
+        self.find_byte(byte).is_some()
+    }
+
+    /// Checks that two C strings are an ASCII case-insensitive match.
+    ///
+    /// Same as `to_ascii_lowercase(a) == to_ascii_lowercase(b)`, but without
+    /// allocating and copying temporaries. Non-ASCII bytes are compared
+    /// exactly, and no UTF-8 validation is performed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drone_core::ffi::CStr;
+    ///
+    /// let a = CStr::from_bytes_with_nul(b"Reset\0").expect("CStr::from_bytes_with_nul failed");
+    /// let b = CStr::from_bytes_with_nul(b"rESET\0").expect("CStr::from_bytes_with_nul failed");
+    /// assert!(a.eq_ignore_ascii_case(b));
+    /// ```
+    #[inline]
+    pub fn eq_ignore_ascii_case(&self, other: &Self) -> bool {
+        self.to_bytes().eq_ignore_ascii_case(other.to_bytes())
+    }
+
+    /// Returns an iterator over the characters of an escaped version of this C
+    /// string, suitable for a Rust byte string literal.
+    ///
+    /// The trailing nul terminator is not included. Printable ASCII bytes
+    /// (`0x20` through `0x7E`) are passed through, except `"` and `\`, which
+    /// are escaped with a backslash. All other bytes are escaped as `\xNN`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drone_core::ffi::CStr;
+    ///
+    /// let c_str =
+    ///     CStr::from_bytes_with_nul(b"say \"hi\"\n\0").expect("CStr::from_bytes_with_nul failed");
+    /// let escaped = c_str.escape_default().collect::<String>();
+    /// assert_eq!(escaped, r#"say \"hi\"\x0a"#);
+    /// ```
+    #[inline]
+    pub fn escape_default(&self) -> EscapeDefault<'_> {
+        EscapeDefault { bytes: self.to_bytes().iter(), buf: [0; 4], pos: 0, len: 0 }
+    }
+
+    /// Returns an iterator over sub-slices of this C string, separated by
+    /// `byte`.
+    ///
+    /// The iterator operates on the bytes without the trailing nul terminator,
+    /// and doesn't require them to be valid UTF-8. Like [`str::split`],
+    /// consecutive delimiters produce empty slices.
+    ///
+    /// # Panics
+    ///
+    /// If `byte` is `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drone_core::ffi::CStr;
+    ///
+    /// let c_str = CStr::from_bytes_with_nul(b"a:b::c\0").expect("CStr::from_bytes_with_nul failed");
+    /// let parts = c_str.split(b':').collect::<Vec<_>>();
+    /// assert_eq!(parts, [&b"a"[..], b"b", b"", b"c"]);
+    /// ```
+    #[inline]
+    pub fn split(&self, byte: u8) -> Split<'_> {
+        assert_ne!(byte, 0, "C string delimiter must not be nul");
+        Split { rest: Some(self.to_bytes()), byte }
+    }
+
+    /// Returns a copy of this C string with leading and trailing runs of
+    /// `byte` removed.
+    ///
+    /// A sub-slice of a C string can't be borrowed as [`CStr`], because its
+    /// terminator would move, so the result is a newly allocated [`CString`].
+    ///
+    /// # Panics
+    ///
+    /// If `byte` is `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drone_core::ffi::CStr;
+    ///
+    /// let c_str = CStr::from_bytes_with_nul(b"  OK \0").expect("CStr::from_bytes_with_nul failed");
+    /// assert_eq!(c_str.trim(b' ').as_bytes(), b"OK");
+    /// ```
+    pub fn trim(&self, byte: u8) -> CString {
+        assert_ne!(byte, 0, "C string trim byte must not be nul");
+        let bytes = self.to_bytes();
+        let start = bytes.iter().position(|&b| b != byte).unwrap_or_else(|| bytes.len());
+        let end = bytes.iter().rposition(|&b| b != byte).map_or(start, |end| end + 1);
+        let mut inner = Vec::with_capacity(end - start + 1);
+        inner.extend_from_slice(&bytes[start..end]);
+        inner.push(0);
+        CString { inner }
+    }
+
+    /// Copies this C string with the nul terminator into the fixed-size field
+    /// `dst`, filling the remainder of `dst` with zeros.
+    ///
+    /// # Errors
+    ///
+    /// If `dst` is shorter than the string with the nul terminator. In this
+    /// case `dst` is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drone_core::ffi::CStr;
+    ///
+    /// let c_str = CStr::from_bytes_with_nul(b"foo\0").expect("CStr::from_bytes_with_nul failed");
+    /// let mut field = [0xFF; 6];
+    /// c_str.copy_into_field(&mut field).expect("field is too small");
+    /// assert_eq!(&field, b"foo\0\0\0");
+    /// ```
+    pub fn copy_into_field(&self, dst: &mut [u8]) -> Result<(), FieldTooSmallError> {
+        let bytes = self.to_bytes_with_nul();
+        if bytes.len() > dst.len() {
+            return Err(FieldTooSmallError { required: bytes.len() });
+        }
+        let (head, tail) = dst.split_at_mut(bytes.len());
+        head.copy_from_slice(bytes);
+        for byte in tail {
+            *byte = 0;
+        }
+        Ok(())
+    }
+
     /// Yields a `&`[`str`] slice if the `CStr` contains valid UTF-8.
     ///
     /// If the contents of the `CStr` are valid UTF-8 data, this function will
@@ -330,7 +649,13 @@ impl CStr {
         // instead of in `from_ptr()`, it may be worth considering if this should
         // be rewritten to do the UTF-8 check inline with the length calculation
         // instead of doing it afterwards.
-        str::from_utf8(self.to_bytes())
+        let bytes = self.to_bytes();
+        if bytes.is_ascii() {
+            // ASCII is always valid UTF-8.
+            Ok(unsafe { str::from_utf8_unchecked(bytes) })
+        } else {
+            str::from_utf8(bytes)
+        }
     }
 
     /// Converts a `CStr` into a [`Cow`]`<`[`str`]`>`.
@@ -377,6 +702,52 @@ impl CStr {
         String::from_utf8_lossy(self.to_bytes())
     }
 
+    /// Splits this C string into the longest valid UTF-8 prefix and the
+    /// remaining bytes.
+    ///
+    /// The tail starts at the first invalid byte, and is not validated
+    /// further. It is empty if the whole string is valid UTF-8. Unlike
+    /// [`CStr::to_string_lossy`], this method never allocates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drone_core::ffi::CStr;
+    ///
+    /// let c_str = CStr::from_bytes_with_nul(b"eth\xFF0\0").unwrap();
+    /// assert_eq!(c_str.to_str_lossy_split(), ("eth", &b"\xFF0"[..]));
+    /// ```
+    pub fn to_str_lossy_split(&self) -> (&str, &[u8]) {
+        let bytes = self.to_bytes();
+        let valid = match str::from_utf8(bytes) {
+            Ok(_) => bytes.len(),
+            Err(err) => err.valid_up_to(),
+        };
+        let (head, tail) = bytes.split_at(valid);
+        (unsafe { str::from_utf8_unchecked(head) }, tail)
+    }
+
+    /// Copies this C string into a newly allocated [`CString`].
+    ///
+    /// The resulting buffer is allocated once with the exact size of the string
+    /// including the nul terminator, and the bytes are copied in a single pass.
+    /// This is what [`ToOwned::to_owned`] uses under the hood.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drone_core::ffi::{CStr, CString};
+    ///
+    /// let c_str = CStr::from_bytes_with_nul(b"foo\0").expect("CStr::from_bytes_with_nul failed");
+    /// assert_eq!(c_str.to_c_string(), CString::new("foo").expect("CString::new failed"));
+    /// ```
+    pub fn to_c_string(&self) -> CString {
+        let bytes = self.to_bytes_with_nul();
+        let mut inner = Vec::with_capacity(bytes.len());
+        inner.extend_from_slice(bytes);
+        CString { inner }
+    }
+
     /// Converts a [`Box`]`<CStr>` into a [`CString`] without copying or
     /// allocating.
     ///
@@ -392,20 +763,55 @@ impl CStr {
     #[allow(clippy::wrong_self_convention)]
     pub fn into_c_string(self: Box<Self>) -> CString {
         let raw = Box::into_raw(self) as *mut [u8];
-        CString { inner: unsafe { Box::from_raw(raw) } }
+        CString { inner: unsafe { Box::from_raw(raw) }.into_vec() }
     }
 }
 
-impl FromBytesWithNulError {
-    fn interior_nul(pos: usize) -> Self {
-        Self { kind: FromBytesWithNulErrorKind::InteriorNul(pos) }
+impl<'a> Iterator for Split<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<&'a [u8]> {
+        let rest = self.rest?;
+        if let Some(position) = memchr::memchr(self.byte, rest) {
+            self.rest = Some(&rest[position + 1..]);
+            Some(&rest[..position])
+        } else {
+            self.rest = None;
+            Some(rest)
+        }
     }
+}
+
+impl FusedIterator for Split<'_> {}
 
-    fn not_nul_terminated() -> Self {
-        Self { kind: FromBytesWithNulErrorKind::NotNulTerminated }
+impl Iterator for EscapeDefault<'_> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        if self.pos < self.len {
+            self.pos += 1;
+            return Some(char::from(self.buf[self.pos - 1]));
+        }
+        let byte = *self.bytes.next()?;
+        match byte {
+            b'"' | b'\\' => {
+                self.buf[..2].copy_from_slice(&[b'\\', byte]);
+                self.len = 2;
+            }
+            0x20..=0x7E => return Some(char::from(byte)),
+            _ => {
+                let hex = b"0123456789abcdef";
+                self.buf = [b'\\', b'x', hex[usize::from(byte >> 4)], hex[usize::from(byte & 0xF)]];
+                self.len = 4;
+            }
+        }
+        self.pos = 1;
+        Some('\\')
     }
 }
 
+impl FusedIterator for EscapeDefault<'_> {}
+
 impl fmt::Debug for CStr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "\"")?;
@@ -433,21 +839,103 @@ impl Eq for CStr {}
 
 impl PartialOrd for CStr {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.to_bytes().partial_cmp(&other.to_bytes())
+        self.to_bytes().partial_cmp(other.to_bytes())
     }
 }
 
 impl Ord for CStr {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.to_bytes().cmp(&other.to_bytes())
+        self.to_bytes().cmp(other.to_bytes())
+    }
+}
+
+trait ContentBytes {
+    fn content_bytes(&self) -> &[u8];
+}
+
+impl ContentBytes for CStr {
+    #[inline]
+    fn content_bytes(&self) -> &[u8] {
+        self.to_bytes()
+    }
+}
+
+impl ContentBytes for CString {
+    #[inline]
+    fn content_bytes(&self) -> &[u8] {
+        self.as_bytes()
     }
 }
 
+impl ContentBytes for str {
+    #[inline]
+    fn content_bytes(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl ContentBytes for [u8] {
+    #[inline]
+    fn content_bytes(&self) -> &[u8] {
+        self
+    }
+}
+
+impl<T: ContentBytes + ?Sized> ContentBytes for &T {
+    #[inline]
+    fn content_bytes(&self) -> &[u8] {
+        (**self).content_bytes()
+    }
+}
+
+// Compares the contents of C strings with byte and string slices, excluding
+// the nul terminator.
+macro_rules! impl_cmp {
+    ($lhs:ty, $rhs:ty) => {
+        impl<'a> PartialEq<$rhs> for $lhs {
+            #[inline]
+            fn eq(&self, other: &$rhs) -> bool {
+                self.content_bytes() == other.content_bytes()
+            }
+        }
+
+        impl<'a> PartialEq<$lhs> for $rhs {
+            #[inline]
+            fn eq(&self, other: &$lhs) -> bool {
+                self.content_bytes() == other.content_bytes()
+            }
+        }
+
+        impl<'a> PartialOrd<$rhs> for $lhs {
+            #[inline]
+            fn partial_cmp(&self, other: &$rhs) -> Option<Ordering> {
+                self.content_bytes().partial_cmp(other.content_bytes())
+            }
+        }
+
+        impl<'a> PartialOrd<$lhs> for $rhs {
+            #[inline]
+            fn partial_cmp(&self, other: &$lhs) -> Option<Ordering> {
+                self.content_bytes().partial_cmp(other.content_bytes())
+            }
+        }
+    };
+}
+
+impl_cmp!(CStr, str);
+impl_cmp!(CStr, &'a str);
+impl_cmp!(CStr, [u8]);
+impl_cmp!(CStr, &'a [u8]);
+impl_cmp!(CString, str);
+impl_cmp!(CString, &'a str);
+impl_cmp!(CString, [u8]);
+impl_cmp!(CString, &'a [u8]);
+
 impl ToOwned for CStr {
     type Owned = CString;
 
     fn to_owned(&self) -> CString {
-        CString { inner: self.to_bytes_with_nul().into() }
+        self.to_c_string()
     }
 }
 
@@ -546,20 +1034,26 @@ impl<'a> From<&'a CString> for Cow<'a, CStr> {
 }
 
 impl fmt::Display for FromBytesWithNulError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.kind)
-    }
-}
-
-impl fmt::Display for FromBytesWithNulErrorKind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            FromBytesWithNulErrorKind::InteriorNul(pos) => {
-                write!(f, "data provided contains an interior nul byte at byte pos {}", pos)
+            FromBytesWithNulError::InteriorNul { position } => {
+                write!(f, "data provided contains an interior nul byte at byte pos {}", position)
             }
-            FromBytesWithNulErrorKind::NotNulTerminated => {
+            FromBytesWithNulError::NotNulTerminated => {
                 write!(f, "data provided is not nul terminated")
             }
         }
     }
 }
+
+impl fmt::Display for MissingNulError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "data provided does not contain a nul")
+    }
+}
+
+impl fmt::Display for FieldTooSmallError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "field is too small, {} bytes required", self.required)
+    }
+}
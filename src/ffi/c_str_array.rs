@@ -0,0 +1,120 @@
+use crate::ffi::{c_char, CStr, CString};
+use core::{fmt, iter::FromIterator, ptr};
+
+/// An owned array of C strings with a null-terminated array of pointers to
+/// them.
+///
+/// This type is useful for interoperating with foreign code which expects an
+/// `argv`-style `char *const argv[]` array. The strings can't be mutated after
+/// they are added, so the pointer array always stays valid.
+///
+/// # Examples
+///
+/// ```
+/// use drone_core::ffi::{c_char, CStrArray, CString};
+///
+/// extern "C" fn vendor_main(_argv: *const *const c_char) {}
+///
+/// let mut argv = CStrArray::new();
+/// argv.push(CString::new("app").expect("CString::new failed"));
+/// argv.push(CString::new("--verbose").expect("CString::new failed"));
+/// vendor_main(argv.as_ptr());
+/// ```
+pub struct CStrArray {
+    strings: Vec<CString>,
+    // Invariant: the vector contains pointers to `strings` followed by a null
+    // pointer.
+    ptrs: Vec<*const c_char>,
+}
+
+unsafe impl Send for CStrArray {}
+unsafe impl Sync for CStrArray {}
+
+impl CStrArray {
+    /// Creates an empty array. The pointer array contains only the terminating
+    /// null pointer.
+    pub fn new() -> Self {
+        Self { strings: Vec::new(), ptrs: vec![ptr::null()] }
+    }
+
+    /// Appends the C string `string` to the end of the array.
+    ///
+    /// The pointer array may be reallocated, so the pointer returned by
+    /// [`CStrArray::as_ptr`] before the call must not be used anymore.
+    pub fn push(&mut self, string: CString) {
+        let last = self.ptrs.len() - 1;
+        self.ptrs.insert(last, string.as_ptr());
+        self.strings.push(string);
+    }
+
+    /// Returns the number of C strings in the array, not counting the
+    /// terminating null pointer.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Returns `true` if the array contains no C strings.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Returns a reference to the C string at `index`, or `None` if out of
+    /// bounds.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&CStr> {
+        self.strings.get(index).map(CString::as_c_str)
+    }
+
+    /// Returns an iterator over the C strings.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &CStr> {
+        self.strings.iter().map(CString::as_c_str)
+    }
+
+    /// Returns a pointer to the null-terminated array of pointers to the C
+    /// strings.
+    ///
+    /// The returned pointer is valid as long as the array is not modified or
+    /// dropped.
+    #[inline]
+    pub fn as_ptr(&self) -> *const *const c_char {
+        self.ptrs.as_ptr()
+    }
+
+    /// Converts the array into the vector of the C strings.
+    #[inline]
+    pub fn into_vec(self) -> Vec<CString> {
+        self.strings
+    }
+}
+
+impl Default for CStrArray {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for CStrArray {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl FromIterator<CString> for CStrArray {
+    fn from_iter<I: IntoIterator<Item = CString>>(iter: I) -> Self {
+        let mut array = Self::new();
+        array.extend(iter);
+        array
+    }
+}
+
+impl Extend<CString> for CStrArray {
+    fn extend<I: IntoIterator<Item = CString>>(&mut self, iter: I) {
+        for string in iter {
+            self.push(string);
+        }
+    }
+}
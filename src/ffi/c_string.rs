@@ -3,7 +3,9 @@
 use crate::ffi::{c_char, libc::strlen, CStr};
 use alloc::borrow::{Borrow, Cow};
 use core::{
-    fmt, mem, ops, ptr,
+    fmt,
+    iter::FromIterator,
+    mem, ops, ptr,
     slice::{self, memchr},
     str::Utf8Error,
 };
@@ -82,10 +84,10 @@ use core::{
 /// memory errors.
 #[derive(PartialEq, PartialOrd, Eq, Ord, Hash, Clone)]
 pub struct CString {
-    // Invariant 1: the slice ends with a zero byte and has a length of at least one.
-    // Invariant 2: the slice contains only one zero byte.
+    // Invariant 1: the vector ends with a zero byte and has a length of at least one.
+    // Invariant 2: the vector contains only one zero byte.
     // Improper usage of unsafe function can break Invariant 2, but not Invariant 1.
-    pub(super) inner: Box<[u8]>,
+    pub(super) inner: Vec<u8>,
 }
 
 /// An error indicating that an interior nul byte was found.
@@ -121,6 +123,22 @@ pub struct IntoStringError {
     error: Utf8Error,
 }
 
+/// An error indicating that a mapping function returned a nul byte.
+///
+/// This `struct` is created by the [`CString::retain_ascii_map`] method on
+/// [`CString`]. See its documentation for more.
+///
+/// # Examples
+///
+/// ```
+/// use drone_core::ffi::{CString, MapNulError};
+///
+/// let mut c_string = CString::new("foo").expect("CString::new failed");
+/// let _: MapNulError = c_string.retain_ascii_map(|_| 0).unwrap_err();
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct MapNulError(usize);
+
 impl CString {
     /// Creates a new C-compatible string from a container of bytes.
     ///
@@ -179,7 +197,158 @@ impl CString {
     pub unsafe fn from_vec_unchecked(mut v: Vec<u8>) -> Self {
         v.reserve_exact(1);
         v.push(0);
-        Self { inner: v.into_boxed_slice() }
+        Self { inner: v }
+    }
+
+    /// Creates a new C-compatible string from an iterator of bytes.
+    ///
+    /// This is a non-panicking alternative to collecting into a `CString` via
+    /// [`FromIterator`](core::iter::FromIterator).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drone_core::ffi::CString;
+    ///
+    /// let c_string = CString::try_from_iter(b"f o o".iter().copied().filter(|&b| b != b' '))
+    ///     .expect("CString::try_from_iter failed");
+    /// assert_eq!(c_string.as_bytes(), b"foo");
+    /// assert!(CString::try_from_iter(b"f\0oo".iter().copied()).is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the iterator yields a 0 byte.
+    /// The [`NulError`] returned will contain the collected bytes as well as
+    /// the position of the nul byte.
+    pub fn try_from_iter<I: IntoIterator<Item = u8>>(iter: I) -> Result<Self, NulError> {
+        Self::_new(iter.into_iter().collect())
+    }
+
+    /// Creates a C-compatible string from a byte vector, which may already be
+    /// nul-terminated, reusing its allocation.
+    ///
+    /// A single trailing 0 byte is stripped, and the rest of the bytes are
+    /// checked like in [`CString::new`]. This is meant to re-validate a buffer
+    /// edited with [`CString::as_vec_mut`] and taken with
+    /// [`CString::into_bytes_with_nul`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drone_core::ffi::CString;
+    ///
+    /// let c_string =
+    ///     CString::from_vec_retaining(b"foo\0".to_vec()).expect("CString::from_vec_retaining failed");
+    /// assert_eq!(c_string.as_bytes_with_nul(), b"foo\0");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the bytes before the trailing
+    /// terminator contain a 0 byte. The [`NulError`] returned will contain the
+    /// bytes without the terminator, as well as the position of the nul byte.
+    pub fn from_vec_retaining(mut v: Vec<u8>) -> Result<Self, NulError> {
+        if v.last() == Some(&0) {
+            v.pop();
+        }
+        Self::_new(v)
+    }
+
+    /// Creates a new C string with the lowercase hexadecimal representation of
+    /// `value`, without a prefix or leading zeros.
+    ///
+    /// The digits are formatted into a stack buffer without the [`core::fmt`]
+    /// machinery, and then copied into a single allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drone_core::ffi::CString;
+    ///
+    /// assert_eq!(CString::from_u32_hex(0xBEEF).as_bytes_with_nul(), b"beef\0");
+    /// ```
+    pub fn from_u32_hex(value: u32) -> Self {
+        let mut buf = [0; 8];
+        let mut pos = buf.len();
+        let mut value = value;
+        loop {
+            pos -= 1;
+            buf[pos] = b"0123456789abcdef"[(value & 0xF) as usize];
+            value >>= 4;
+            if value == 0 {
+                break;
+            }
+        }
+        Self::from_digits(&buf[pos..])
+    }
+
+    /// Creates a new C string with the decimal representation of `value`.
+    ///
+    /// The digits are formatted into a stack buffer without the [`core::fmt`]
+    /// machinery, and then copied into a single allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drone_core::ffi::CString;
+    ///
+    /// assert_eq!(CString::from_u64_dec(1024).as_bytes_with_nul(), b"1024\0");
+    /// ```
+    pub fn from_u64_dec(value: u64) -> Self {
+        let mut buf = [0; 20];
+        let mut pos = buf.len();
+        let mut value = value;
+        loop {
+            pos -= 1;
+            buf[pos] = b'0' + (value % 10) as u8;
+            value /= 10;
+            if value == 0 {
+                break;
+            }
+        }
+        Self::from_digits(&buf[pos..])
+    }
+
+    fn from_digits(digits: &[u8]) -> Self {
+        let mut inner = Vec::with_capacity(digits.len() + 1);
+        inner.extend_from_slice(digits);
+        inner.push(0);
+        Self { inner }
+    }
+
+    /// Creates a new C string by concatenating `parts` with `sep` between
+    /// them.
+    ///
+    /// The total length is computed first, so the string is allocated once. An
+    /// empty `parts` slice yields an empty C string.
+    ///
+    /// # Panics
+    ///
+    /// If `sep` is a nul byte.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drone_core::ffi::CString;
+    ///
+    /// let usr = CString::new("usr").unwrap();
+    /// let bin = CString::new("bin").unwrap();
+    /// let path = CString::join(b'/', &[usr.as_c_str(), bin.as_c_str()]);
+    /// assert_eq!(path.as_bytes_with_nul(), b"usr/bin\0");
+    /// ```
+    pub fn join(sep: u8, parts: &[&CStr]) -> Self {
+        assert_ne!(sep, 0, "separator must be non-zero");
+        let len = parts.iter().map(|part| part.to_bytes().len() + 1).sum::<usize>();
+        let mut inner = Vec::with_capacity(len.max(1));
+        for (i, part) in parts.iter().enumerate() {
+            if i > 0 {
+                inner.push(sep);
+            }
+            inner.extend_from_slice(part.to_bytes());
+        }
+        inner.push(0);
+        Self { inner }
     }
 
     /// Retakes ownership of a `CString` that was transferred to C via
@@ -222,7 +391,7 @@ impl CString {
     pub unsafe fn from_raw(ptr: *mut c_char) -> Self {
         let len = strlen(ptr) + 1; // Including the NUL byte
         let slice = slice::from_raw_parts_mut(ptr, len as usize);
-        Self { inner: Box::from_raw(slice as *mut [c_char] as *mut [u8]) }
+        Self { inner: Box::from_raw(slice as *mut [c_char] as *mut [u8]).into_vec() }
     }
 
     /// Consumes the `CString` and transfers ownership of the string to a C
@@ -256,7 +425,7 @@ impl CString {
     /// ```
     #[inline]
     pub fn into_raw(self) -> *mut c_char {
-        Box::into_raw(self.into_inner()) as *mut c_char
+        Box::into_raw(self.into_inner().into_boxed_slice()) as *mut c_char
     }
 
     /// Converts the `CString` into a [`String`] if it contains valid UTF-8
@@ -264,6 +433,9 @@ impl CString {
     ///
     /// On failure, ownership of the original `CString` is returned.
     ///
+    /// The buffer of the `CString` is reused for the resulting [`String`], so
+    /// no reallocation occurs on success.
+    ///
     /// # Examples
     ///
     /// ```
@@ -285,6 +457,30 @@ impl CString {
         })
     }
 
+    /// Converts the `CString` into a [`String`], replacing any invalid UTF-8
+    /// sequences with [`U+FFFD REPLACEMENT
+    /// CHARACTER`](core::char::REPLACEMENT_CHARACTER).
+    ///
+    /// If the `CString` contains valid UTF-8 data, its buffer is reused for the
+    /// resulting [`String`] without reallocation. Otherwise a new [`String`] is
+    /// allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drone_core::ffi::CString;
+    ///
+    /// let cstring = CString::new(b"foo".to_vec()).expect("CString::new failed");
+    /// assert_eq!(cstring.into_string_lossy(), "foo");
+    ///
+    /// let cstring = CString::new(b"f\xFFoo".to_vec()).expect("CString::new failed");
+    /// assert_eq!(cstring.into_string_lossy(), "f\u{FFFD}oo");
+    /// ```
+    pub fn into_string_lossy(self) -> String {
+        String::from_utf8(self.into_bytes())
+            .unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned())
+    }
+
     /// Consumes the `CString` and returns the underlying byte buffer.
     ///
     /// The returned buffer does **not** contain the trailing nul terminator,
@@ -300,7 +496,7 @@ impl CString {
     /// assert_eq!(bytes, vec![b'f', b'o', b'o']);
     /// ```
     pub fn into_bytes(self) -> Vec<u8> {
-        let mut vec = self.into_inner().into_vec();
+        let mut vec = self.into_inner();
         let nul = vec.pop();
         debug_assert_eq!(nul, Some(0_u8));
         vec
@@ -319,7 +515,7 @@ impl CString {
     /// assert_eq!(bytes, vec![b'f', b'o', b'o', b'\0']);
     /// ```
     pub fn into_bytes_with_nul(self) -> Vec<u8> {
-        self.into_inner().into_vec()
+        self.into_inner()
     }
 
     /// Returns the contents of this `CString` as a slice of bytes.
@@ -342,6 +538,95 @@ impl CString {
         &self.inner[..self.inner.len() - 1]
     }
 
+    /// Returns the contents of this `CString` as a mutable slice of bytes.
+    ///
+    /// The returned slice does **not** contain the trailing nul terminator.
+    /// For a safe alternative see [`CString::retain_ascii_map`].
+    ///
+    /// # Safety
+    ///
+    /// The caller must not write a nul byte into the slice, as that would
+    /// leave an interior nul byte in the string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drone_core::ffi::CString;
+    ///
+    /// let mut c_string = CString::new("foo").expect("CString::new failed");
+    /// unsafe { c_string.as_mut_bytes() }.make_ascii_uppercase();
+    /// assert_eq!(c_string.as_bytes(), b"FOO");
+    /// ```
+    #[inline]
+    pub unsafe fn as_mut_bytes(&mut self) -> &mut [u8] {
+        let len = self.inner.len() - 1;
+        &mut self.inner[..len]
+    }
+
+    /// Returns a mutable reference to the backing vector of this `CString`,
+    /// including the trailing nul terminator.
+    ///
+    /// Unlike [`CString::as_mut_bytes`], this allows changing the length of
+    /// the string in place, e.g. for bulk rewrites which
+    /// [`CString::retain`] and [`CString::truncate`] can't express.
+    ///
+    /// # Safety
+    ///
+    /// This is a very dangerous method. The vector must not be left empty, as
+    /// dropping the `CString` writes to its first byte. Before the `CString` is
+    /// used in any other way than [`CString::into_bytes_with_nul`], the vector
+    /// must end with a nul byte and must contain no other nul bytes. If the
+    /// edit may break that, pass the result of
+    /// [`CString::into_bytes_with_nul`] to [`CString::from_vec_retaining`] to
+    /// check it again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drone_core::ffi::CString;
+    ///
+    /// let mut c_string = CString::new("foo").expect("CString::new failed");
+    /// unsafe { c_string.as_vec_mut() }.splice(0..0, b"bar/".iter().copied());
+    /// let c_string = CString::from_vec_retaining(c_string.into_bytes_with_nul())
+    ///     .expect("CString::from_vec_retaining failed");
+    /// assert_eq!(c_string.as_bytes(), b"bar/foo");
+    /// ```
+    #[inline]
+    pub unsafe fn as_vec_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.inner
+    }
+
+    /// Replaces each byte of this `CString` with the result of `f`, not
+    /// counting the nul terminator.
+    ///
+    /// This is intended for in-place transforms like ASCII case conversion.
+    ///
+    /// # Errors
+    ///
+    /// If `f` returns a nul byte, the method stops and returns an error with
+    /// the position of the byte. The bytes before the position are already
+    /// mapped, and the rest, including the byte at the position, are left
+    /// unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drone_core::ffi::CString;
+    ///
+    /// let mut c_string = CString::new("usart1").expect("CString::new failed");
+    /// c_string.retain_ascii_map(|b| b.to_ascii_uppercase()).unwrap();
+    /// assert_eq!(c_string.as_bytes(), b"USART1");
+    /// ```
+    pub fn retain_ascii_map<F: FnMut(u8) -> u8>(&mut self, mut f: F) -> Result<(), MapNulError> {
+        for (pos, byte) in unsafe { self.as_mut_bytes() }.iter_mut().enumerate() {
+            match f(*byte) {
+                0 => return Err(MapNulError(pos)),
+                mapped => *byte = mapped,
+            }
+        }
+        Ok(())
+    }
+
     /// Equivalent to the [`CString::as_bytes`] function except that the
     /// returned slice includes the trailing nul terminator.
     ///
@@ -393,15 +678,122 @@ impl CString {
     /// );
     /// ```
     pub fn into_boxed_c_str(self) -> Box<CStr> {
-        unsafe { Box::from_raw(Box::into_raw(self.into_inner()) as *mut CStr) }
+        unsafe { Box::from_raw(Box::into_raw(self.into_inner().into_boxed_slice()) as *mut CStr) }
+    }
+
+    /// Shortens this `CString` to the first `new_len` bytes, not counting the
+    /// nul terminator.
+    ///
+    /// The nul terminator is moved to the new end of the string. If `new_len`
+    /// is greater than or equal to the current length, this has no effect.
+    ///
+    /// Note that this method has no effect on the allocated capacity of the
+    /// string, so the buffer can be re-grown without reallocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drone_core::ffi::CString;
+    ///
+    /// let mut c_string = CString::new("foobar").expect("CString::new failed");
+    /// c_string.truncate(3);
+    /// assert_eq!(c_string.as_bytes_with_nul(), b"foo\0");
+    /// ```
+    pub fn truncate(&mut self, new_len: usize) {
+        if new_len < self.as_bytes().len() {
+            self.inner.truncate(new_len);
+            self.inner.push(0);
+        }
+    }
+
+    /// Retains only the bytes specified by the predicate, not counting the nul
+    /// terminator.
+    ///
+    /// In other words, removes all bytes `b` such that `f(b)` returns `false`.
+    /// The remaining bytes are compacted in place and keep their order. Since
+    /// bytes can only be removed, the result is always a valid C string.
+    ///
+    /// Note that this method has no effect on the allocated capacity of the
+    /// string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drone_core::ffi::CString;
+    ///
+    /// let mut c_string = CString::new("eth\t0\x7f").expect("CString::new failed");
+    /// c_string.retain(|b| !b.is_ascii_control());
+    /// assert_eq!(c_string.as_bytes_with_nul(), b"eth0\0");
+    /// ```
+    pub fn retain<F: FnMut(u8) -> bool>(&mut self, mut f: F) {
+        self.inner.retain(|&b| b == 0 || f(b));
+    }
+
+    /// Appends the contents of `other` to the end of this `CString`.
+    ///
+    /// The nul terminator of `other` is not copied, and this `CString` keeps a
+    /// single trailing nul terminator. Since a [`CStr`] never contains interior
+    /// nul bytes, the result is always a valid C string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drone_core::ffi::{CStr, CString};
+    ///
+    /// let mut path = CString::new("/dev/").expect("CString::new failed");
+    /// let name = CStr::from_bytes_with_nul(b"tty0\0").expect("CStr::from_bytes_with_nul failed");
+    /// path.push_cstr(name);
+    /// assert_eq!(path.as_bytes_with_nul(), b"/dev/tty0\0");
+    /// ```
+    pub fn push_cstr(&mut self, other: &CStr) {
+        self.inner.pop();
+        self.inner.extend_from_slice(other.to_bytes_with_nul());
+    }
+
+    /// Returns the number of bytes the string can hold without reallocating,
+    /// including the nul terminator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drone_core::ffi::CString;
+    ///
+    /// let c_string = CString::new(Vec::with_capacity(16)).expect("CString::new failed");
+    /// assert_eq!(c_string.capacity(), 16);
+    /// ```
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+
+    /// Shrinks the capacity of the string to fit its bytes and the nul
+    /// terminator.
+    ///
+    /// This is a no-op if there is no excess capacity. Otherwise the buffer is
+    /// reallocated, which is useful before handing off an incrementally built
+    /// string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drone_core::ffi::CString;
+    ///
+    /// let mut c_string = CString::new(Vec::with_capacity(16)).expect("CString::new failed");
+    /// c_string.extend(b"foo".iter().copied());
+    /// c_string.shrink_to_fit();
+    /// assert_eq!(c_string.capacity(), 4);
+    /// ```
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.inner.shrink_to_fit();
     }
 
     /// Bypass "move out of struct which implements [`Drop`] trait" restriction.
-    pub(super) fn into_inner(self) -> Box<[u8]> {
+    pub(super) fn into_inner(self) -> Vec<u8> {
         // Rationale: `mem::forget(self)` invalidates the previous call to
         // `ptr::read(&self.inner)` so we use `ManuallyDrop` to ensure `self` is
-        // not dropped. Then we can return the box directly without invalidating
-        // it. See https://github.com/rust-lang/rust/issues/62553.
+        // not dropped. Then we can return the vector directly without
+        // invalidating it. See https://github.com/rust-lang/rust/issues/62553.
         let this = mem::ManuallyDrop::new(self);
         unsafe { ptr::read(&this.inner) }
     }
@@ -440,6 +832,26 @@ impl NulError {
     pub fn into_vec(self) -> Vec<u8> {
         self.1
     }
+
+    /// Consumes this error, returning a [`CString`] made of the bytes before
+    /// the nul byte.
+    ///
+    /// The nul byte becomes the terminator of the returned string, so the
+    /// underlying buffer is reused.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drone_core::ffi::CString;
+    ///
+    /// let nul_error = CString::new("foo\0bar").unwrap_err();
+    /// assert_eq!(nul_error.into_prefix_cstring().as_bytes(), b"foo");
+    /// ```
+    pub fn into_prefix_cstring(self) -> CString {
+        let Self(nul_position, mut bytes) = self;
+        bytes.truncate(nul_position + 1);
+        CString { inner: bytes }
+    }
 }
 
 impl IntoStringError {
@@ -455,6 +867,24 @@ impl IntoStringError {
     }
 }
 
+impl MapNulError {
+    /// Returns the position of the byte, for which the mapping function
+    /// returned nul.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drone_core::ffi::CString;
+    ///
+    /// let mut c_string = CString::new("foo").expect("CString::new failed");
+    /// let err = c_string.retain_ascii_map(|b| if b == b'o' { 0 } else { b }).unwrap_err();
+    /// assert_eq!(err.nul_position(), 1);
+    /// ```
+    pub fn nul_position(self) -> usize {
+        self.0
+    }
+}
+
 // Turns this `CString` into an empty string to prevent memory unsafe code from
 // working by accident. Inline to prevent LLVM from optimizing it away in debug
 // builds.
@@ -491,6 +921,74 @@ impl From<CString> for Vec<u8> {
     }
 }
 
+impl FromIterator<u8> for CString {
+    /// Collects bytes into a [`CString`].
+    ///
+    /// # Panics
+    ///
+    /// If the iterator yields a 0 byte. Use [`CString::try_from_iter`] to
+    /// handle this case.
+    fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> Self {
+        match Self::try_from_iter(iter) {
+            Ok(s) => s,
+            Err(err) => panic!("{}", err),
+        }
+    }
+}
+
+impl Extend<u8> for CString {
+    /// Appends bytes to the end of the [`CString`].
+    ///
+    /// # Panics
+    ///
+    /// If the iterator yields a 0 byte. The string is left unchanged in this
+    /// case.
+    fn extend<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+        // Extend after the nul terminator, and cut the extension off if the
+        // iterator panics, so the invariants are upheld during unwinding.
+        let len = self.inner.len();
+        let guard = Truncate { vec: &mut self.inner, len };
+        guard.vec.extend(iter);
+        if let Some(i) = memchr::memchr(0, &guard.vec[len..]) {
+            drop(guard);
+            panic!("nul byte found in provided data at position: {}", i);
+        }
+        mem::forget(guard);
+        // Move the nul terminator to the end.
+        self.inner[len - 1..].rotate_left(1);
+    }
+}
+
+// Truncates the vector back to `len` when dropped.
+struct Truncate<'a> {
+    vec: &'a mut Vec<u8>,
+    len: usize,
+}
+
+impl Drop for Truncate<'_> {
+    fn drop(&mut self) {
+        self.vec.truncate(self.len);
+    }
+}
+
+impl fmt::Write for CString {
+    /// Appends the string slice `s` to the end of the [`CString`].
+    ///
+    /// # Errors
+    ///
+    /// If `s` contains a nul byte. The string is left unchanged in this case,
+    /// though with [`write!`] the pieces formatted before `s` are kept.
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if memchr::memchr(0, s.as_bytes()).is_some() {
+            return Err(fmt::Error);
+        }
+        self.inner.pop();
+        self.inner.extend_from_slice(s.as_bytes());
+        self.inner.push(0);
+        Ok(())
+    }
+}
+
 impl Default for CString {
     /// Creates an empty `CString`.
     fn default() -> Self {
@@ -555,3 +1053,9 @@ impl fmt::Display for IntoStringError {
         write!(f, "C string contained non-utf8 bytes")
     }
 }
+
+impl fmt::Display for MapNulError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "nul byte returned by the mapping at position: {}", self.0)
+    }
+}
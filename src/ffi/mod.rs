@@ -1,7 +1,9 @@
 //! Utilities related to FFI bindings.
 //!
 //! This module is an analogue of [`std::ffi`] (without `OsStr`/`OsString`) and
-//! [`std::os::raw`]. See their documentations for more details.
+//! [`std::os::raw`]. See their documentations for more details. Additionally
+//! it provides [`WCStr`] and [`WCString`] for nul-terminated UTF-16 strings,
+//! and [`CStrArray`] for `argv`-style arrays of C strings.
 //!
 //! [`std::ffi`]: https://doc.rust-lang.org/std/ffi/
 //! [`std::os::raw`]: https://doc.rust-lang.org/std/os/raw/
@@ -9,27 +11,66 @@
 pub mod libc;
 
 mod c_str;
+mod c_str_array;
 mod c_string;
+mod wc_str;
+mod wc_string;
 
 #[doc(no_inline)]
 pub use drone_ctypes::*;
 
 pub use self::{
-    c_str::{CStr, FromBytesWithNulError},
-    c_string::{CString, IntoStringError, NulError},
+    c_str::{
+        CStr, EscapeDefault, FieldTooSmallError, FromBytesWithNulError, MissingNulError, Split,
+    },
+    c_str_array::CStrArray,
+    c_string::{CString, IntoStringError, MapNulError, NulError},
+    wc_str::{FromWideWithNulError, WCStr},
+    wc_string::{WCString, WNulError},
 };
 
+/// Returns the index of the first occurrence of `needle` in `haystack`.
+///
+/// This is the search used by [`CString::new`] to find interior nul bytes. To
+/// measure a raw nul-terminated string use [`libc::strlen`].
+///
+/// # Examples
+///
+/// ```
+/// use drone_core::ffi::memchr;
+///
+/// assert_eq!(memchr(0, b"foo\0bar"), Some(3));
+/// assert_eq!(memchr(0, b"foo"), None);
+/// ```
+#[inline]
+pub fn memchr(needle: u8, haystack: &[u8]) -> Option<usize> {
+    core::slice::memchr::memchr(needle, haystack)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use alloc::{
-        borrow::Cow::{Borrowed, Owned},
+        borrow::Cow::{self, Borrowed, Owned},
         rc::Rc,
         sync::Arc,
     };
-    use core::hash::{Hash, Hasher};
+    use core::{
+        hash::{Hash, Hasher},
+        ptr, slice, str,
+    };
     use std::collections::hash_map::DefaultHasher;
 
+    #[test]
+    fn memchr_positions() {
+        assert_eq!(memchr(0, b""), None);
+        assert_eq!(memchr(0, b"foo"), None);
+        assert_eq!(memchr(0, b"\0foo"), Some(0));
+        assert_eq!(memchr(0, b"foo\0"), Some(3));
+        assert_eq!(memchr(b'o', b"foo\0"), Some(1));
+        assert_eq!(memchr(0, b"a long string without nul bytes, spanning words\0"), Some(47));
+    }
+
     #[test]
     fn c_to_rust() {
         let data = b"123\0";
@@ -51,6 +92,16 @@ mod tests {
     fn build_with_zero1() {
         assert!(CString::new(&b"\0"[..]).is_err());
     }
+
+    #[test]
+    fn nul_error_into_prefix_cstring() {
+        let s = CString::new("foo\0bar").unwrap_err().into_prefix_cstring();
+        assert_eq!(s, CString::new("foo").unwrap());
+        assert_eq!(s.as_bytes_with_nul(), b"foo\0");
+        let s = CString::new("\0bar").unwrap_err().into_prefix_cstring();
+        assert_eq!(s.as_bytes_with_nul(), b"\0");
+    }
+
     #[test]
     fn build_with_zero2() {
         assert!(CString::new(vec![0]).is_err());
@@ -79,6 +130,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn count_bytes() {
+        let empty: &CStr = Default::default();
+        assert_eq!(empty.count_bytes(), 0);
+        let s = CString::new("123\u{2026}").unwrap();
+        assert_eq!(s.count_bytes(), 6);
+        assert_eq!(s.count_bytes(), s.to_bytes().len());
+    }
+
+    #[test]
+    fn cmp_str() {
+        let s = CString::new("OK").unwrap();
+        assert_eq!(s, "OK");
+        assert_eq!("OK", s);
+        assert_eq!(*s, "OK");
+        assert_eq!(*s, *"OK");
+        assert_ne!(s, "OK\0");
+        assert_ne!(s, "O");
+        assert!(s < "OL");
+        assert!("OJ" < s);
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn cmp_bytes() {
+        let s = CString::new(&b"a\xFF"[..]).unwrap();
+        assert_eq!(s, &b"a\xFF"[..]);
+        assert_eq!(&b"a\xFF"[..], *s);
+        assert_ne!(s, &b"a\xFF\0"[..]);
+        assert!(s > &b"a\x7F"[..]);
+        assert!(*s > *"a\u{7F}");
+    }
+
     #[test]
     fn to_str() {
         let data = b"123\xE2\x80\xA6\0";
@@ -95,6 +179,60 @@ mod tests {
         }
     }
 
+    #[test]
+    fn cow_c_str_borrowed() {
+        let c_str = CStr::from_bytes_with_nul(b"foo\0").unwrap();
+        let cow = Cow::from(c_str);
+        assert!(match cow {
+            Borrowed(borrowed) => ptr::eq(borrowed, c_str),
+            Owned(_) => false,
+        });
+        assert_eq!(cow.to_bytes(), b"foo");
+    }
+
+    #[test]
+    fn cow_c_str_to_mut() {
+        let c_str = CStr::from_bytes_with_nul(b"foo\0").unwrap();
+        let mut cow = Cow::from(c_str);
+        cow.to_mut().push_cstr(CStr::from_bytes_with_nul(b"bar\0").unwrap());
+        assert!(match cow {
+            Borrowed(_) => false,
+            Owned(ref owned) => owned.as_bytes() == b"foobar",
+        });
+        assert_eq!(c_str.to_bytes(), b"foo");
+    }
+
+    #[test]
+    fn to_str_ascii_fast_path() {
+        let inputs: &[&[u8]] = &[
+            b"\0",
+            b"usart1\0",
+            b"a long device name over several words\0",
+            b"123\xE2\x80\xA6\0",
+            b"a long device name \xE2\x80\xA6 with a multibyte char\0",
+            b"123\xE2\0",
+            b"a long device name with a trailing invalid byte \xFF\0",
+        ];
+        for input in inputs {
+            let c_str = CStr::from_bytes_with_nul(input).unwrap();
+            assert_eq!(c_str.to_str(), str::from_utf8(c_str.to_bytes()));
+        }
+    }
+
+    #[test]
+    fn into_string_reuses_buffer() {
+        let s = CString::new("foo").unwrap();
+        let ptr = s.as_ptr();
+        let string = s.into_string().unwrap();
+        assert_eq!(string, "foo");
+        assert_eq!(string.as_ptr(), ptr);
+        let s = CString::new("bar").unwrap();
+        let lossy_ptr = s.as_ptr();
+        let lossy = s.into_string_lossy();
+        assert_eq!(lossy, "bar");
+        assert_eq!(lossy.as_ptr(), lossy_ptr);
+    }
+
     #[test]
     fn to_owned() {
         let data = b"123\0";
@@ -104,6 +242,17 @@ mod tests {
         assert_eq!(owned.as_bytes_with_nul(), data);
     }
 
+    #[test]
+    fn to_c_string_exact() {
+        let data = vec![b'a'; 1024];
+        let s = CString::new(data.clone()).unwrap();
+        let copy = s.as_c_str().to_c_string();
+        assert_eq!(copy, s);
+        assert_eq!(copy.as_bytes(), &data[..]);
+        assert_eq!(copy.inner.capacity(), 1025);
+        assert_eq!(s.as_c_str().to_owned().inner.capacity(), 1025);
+    }
+
     #[test]
     fn equal_hash() {
         let data = b"123\xE2\xFA\xA6\0";
@@ -140,6 +289,7 @@ mod tests {
         let data = b"123";
         let cstr = CStr::from_bytes_with_nul(data);
         assert!(cstr.is_err());
+        assert_eq!(cstr, Err(FromBytesWithNulError::NotNulTerminated));
     }
 
     #[test]
@@ -147,6 +297,71 @@ mod tests {
         let data = b"1\023\0";
         let cstr = CStr::from_bytes_with_nul(data);
         assert!(cstr.is_err());
+        let data = b"12\03\0";
+        let cstr = CStr::from_bytes_with_nul(data);
+        assert_eq!(cstr, Err(FromBytesWithNulError::InteriorNul { position: 2 }));
+    }
+
+    #[test]
+    fn push_cstr_empty() {
+        let empty: &CStr = Default::default();
+        let mut s = CString::new("foo").unwrap();
+        s.push_cstr(empty);
+        assert_eq!(s.as_bytes_with_nul(), b"foo\0");
+        let mut s = CString::default();
+        s.push_cstr(&CString::new("foo").unwrap());
+        assert_eq!(s.as_bytes_with_nul(), b"foo\0");
+    }
+
+    #[test]
+    fn collect() {
+        let s: CString = b"f-o-o".iter().copied().filter(|&b| b != b'-').collect();
+        assert_eq!(s.as_bytes_with_nul(), b"foo\0");
+        let mut s = s;
+        s.extend(b"bar".iter().copied());
+        assert_eq!(s.as_bytes_with_nul(), b"foobar\0");
+    }
+
+    #[test]
+    #[should_panic]
+    fn collect_interior_nul() {
+        let _: CString = b"f\0oo".iter().copied().collect();
+    }
+
+    #[test]
+    #[should_panic]
+    fn extend_interior_nul() {
+        CString::new("foo").unwrap().extend(b"b\0ar".iter().copied());
+    }
+
+    #[test]
+    fn extend_panicking_iter() {
+        extern crate std;
+        let mut s = CString::new("foo").unwrap();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            s.extend(b"bar".iter().map(|&b| if b == b'r' { panic!() } else { b }));
+        }));
+        assert!(result.is_err());
+        assert_eq!(s.as_bytes_with_nul(), b"foo\0");
+    }
+
+    #[test]
+    fn from_bytes_until_nul_packed() {
+        let mut rest = &b"foo\0\0bar\0"[..];
+        let mut strings = Vec::new();
+        while !rest.is_empty() {
+            let (cstr, tail) = CStr::from_bytes_until_nul(rest).unwrap();
+            strings.push(cstr.to_bytes());
+            rest = tail;
+        }
+        assert_eq!(strings, [&b"foo"[..], b"", b"bar"]);
+    }
+
+    #[test]
+    fn from_bytes_until_nul_unterminated() {
+        let (cstr, rest) = CStr::from_bytes_until_nul(b"foo\0bar").unwrap();
+        assert_eq!(cstr.to_bytes_with_nul(), b"foo\0");
+        assert!(CStr::from_bytes_until_nul(rest).is_err());
     }
 
     #[test]
@@ -183,10 +398,336 @@ mod tests {
         assert_eq!(&*arc2, cstr);
     }
 
+    #[test]
+    fn retain_ascii_map_uppercase() {
+        let mut s = CString::new("usart1_tx").unwrap();
+        s.retain_ascii_map(|b| b.to_ascii_uppercase()).unwrap();
+        assert_eq!(s.as_bytes_with_nul(), b"USART1_TX\0");
+    }
+
+    #[test]
+    fn retain_ascii_map_nul() {
+        let mut s = CString::new("abcb").unwrap();
+        let err = s.retain_ascii_map(|b| if b == b'c' { 0 } else { b.to_ascii_uppercase() });
+        assert_eq!(err.map_err(MapNulError::nul_position), Err(2));
+        assert_eq!(s.as_bytes_with_nul(), b"ABcb\0");
+    }
+
+    #[test]
+    fn truncate_to_zero() {
+        let mut s = CString::new("foobar").unwrap();
+        let capacity = s.inner.capacity();
+        s.truncate(0);
+        assert_eq!(s.as_bytes_with_nul(), b"\0");
+        assert_eq!(s.inner.capacity(), capacity);
+    }
+
+    #[test]
+    fn truncate_to_current_len() {
+        let mut s = CString::new("foobar").unwrap();
+        s.truncate(6);
+        assert_eq!(s.as_bytes_with_nul(), b"foobar\0");
+        s.truncate(10);
+        assert_eq!(s.as_bytes_with_nul(), b"foobar\0");
+    }
+
+    #[test]
+    fn retain_ascii_letters() {
+        let mut s = CString::new(&b"a1\xffB-\x01c"[..]).unwrap();
+        let capacity = s.inner.capacity();
+        s.retain(|b| b.is_ascii_alphabetic());
+        assert_eq!(s.as_bytes_with_nul(), b"aBc\0");
+        assert_eq!(s.inner.capacity(), capacity);
+    }
+
+    #[test]
+    fn retain_nothing() {
+        let mut s = CString::new("foobar").unwrap();
+        s.retain(|_| false);
+        assert_eq!(s.as_bytes_with_nul(), b"\0");
+        assert!(s.as_c_str().to_bytes().is_empty());
+    }
+
+    #[test]
+    fn shrink_to_fit_after_extend() {
+        let mut s = CString::new(Vec::with_capacity(64)).unwrap();
+        s.extend(b"abc".iter().copied());
+        assert_eq!(s.capacity(), 64);
+        s.shrink_to_fit();
+        assert_eq!(s.capacity(), 4);
+        assert_eq!(s.as_bytes_with_nul(), b"abc\0");
+        s.shrink_to_fit();
+        assert_eq!(s.capacity(), 4);
+    }
+
+    #[test]
+    fn wide_surrogate_pairs() {
+        let s = WCString::from_str("a\u{1D11E}b").unwrap();
+        assert_eq!(s.as_wide_with_nul(), &[0x61, 0xD834, 0xDD1E, 0x62, 0]);
+        assert_eq!(s.to_string().unwrap(), "a\u{1D11E}b");
+        let unpaired = WCString::new(vec![0x61, 0xD834]).unwrap();
+        assert!(unpaired.to_string().is_err());
+        assert_eq!(unpaired.to_string_lossy(), "a\u{FFFD}");
+        assert_eq!(format!("{:?}", unpaired), r#""a\u{d834}""#);
+    }
+
+    #[test]
+    fn wide_interior_nul() {
+        let err = WCString::new(vec![0x61, 0, 0x62]).unwrap_err();
+        assert_eq!(err.nul_position(), 1);
+        assert_eq!(err.into_vec(), [0x61, 0, 0x62]);
+        assert!(WCString::from_str("a\0b").is_err());
+        assert_eq!(
+            WCStr::from_wide_with_nul(&[0x61, 0, 0x62, 0]).unwrap_err(),
+            FromWideWithNulError::InteriorNul { position: 1 }
+        );
+        assert_eq!(
+            WCStr::from_wide_with_nul(&[0x61, 0x62]).unwrap_err(),
+            FromWideWithNulError::NotNulTerminated
+        );
+    }
+
+    #[test]
+    fn wide_raw() {
+        let s = WCString::from_str("foo").unwrap();
+        let ptr = s.into_raw();
+        unsafe {
+            assert_eq!(WCStr::from_ptr(ptr).as_wide(), &[0x66, 0x6F, 0x6F]);
+            let s = WCString::from_raw(ptr);
+            assert_eq!(s.into_vec(), [0x66, 0x6F, 0x6F]);
+        }
+    }
+
     #[test]
     fn cstr_const_constructor() {
         const CSTR: &CStr = unsafe { CStr::from_bytes_with_nul_unchecked(b"Hello, world!\0") };
 
         assert_eq!(CSTR.to_str().unwrap(), "Hello, world!");
     }
+
+    #[test]
+    fn split_c_str() {
+        let c_str = CStr::from_bytes_with_nul(b"a:b::c\0").unwrap();
+        let mut split = c_str.split(b':');
+        assert_eq!(split.next(), Some(&b"a"[..]));
+        assert_eq!(split.next(), Some(&b"b"[..]));
+        assert_eq!(split.next(), Some(&b""[..]));
+        assert_eq!(split.next(), Some(&b"c"[..]));
+        assert_eq!(split.next(), None);
+        assert_eq!(split.next(), None);
+        let non_utf8 = CStr::from_bytes_with_nul(b"\xff:\0").unwrap();
+        assert_eq!(non_utf8.split(b':').collect::<Vec<_>>(), [&b"\xff"[..], b""]);
+        let empty = CStr::from_bytes_with_nul(b"\0").unwrap();
+        assert_eq!(empty.split(b':').collect::<Vec<_>>(), [&b""[..]]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn split_c_str_nul() {
+        let c_str = CStr::from_bytes_with_nul(b"a\0").unwrap();
+        c_str.split(0);
+    }
+
+    #[test]
+    fn escape_default_c_str() {
+        let c_str = CStr::from_bytes_with_nul(b"a\t\"\x80\\~\0").unwrap();
+        let escaped = c_str.escape_default().collect::<String>();
+        assert_eq!(escaped, r#"a\x09\"\x80\\~"#);
+        assert_eq!(c_str.escape_default().count(), escaped.len());
+    }
+
+    #[test]
+    fn write_fmt() {
+        use core::fmt::Write;
+        let mut s = CString::default();
+        let name = "usart1";
+        write!(s, "reg={:#x} {}", 0x2B, name).unwrap();
+        assert_eq!(s.as_bytes_with_nul(), b"reg=0x2b usart1\0");
+        assert_eq!(s.write_str("a\0b"), Err(core::fmt::Error));
+        assert_eq!(s.as_bytes_with_nul(), b"reg=0x2b usart1\0");
+    }
+
+    #[test]
+    fn c_str_array_null_terminated() {
+        let mut array = CStrArray::new();
+        assert!(array.is_empty());
+        assert!(unsafe { *array.as_ptr() }.is_null());
+        array.push(CString::new("foo").unwrap());
+        array.push(CString::new("bar").unwrap());
+        assert_eq!(array.len(), 2);
+        let ptrs = unsafe { slice::from_raw_parts(array.as_ptr(), 3) };
+        assert_eq!(unsafe { CStr::from_ptr(ptrs[0]) }.to_bytes(), b"foo");
+        assert_eq!(unsafe { CStr::from_ptr(ptrs[1]) }.to_bytes(), b"bar");
+        assert!(ptrs[2].is_null());
+        assert_eq!(array.get(1).unwrap().to_bytes(), b"bar");
+        assert!(array.get(2).is_none());
+    }
+
+    #[test]
+    fn c_str_array_from_iter() {
+        let array = vec![CString::new("a").unwrap(), CString::new("b").unwrap()]
+            .into_iter()
+            .collect::<CStrArray>();
+        assert_eq!(format!("{:?}", array), r#"["a", "b"]"#);
+        let ptrs = unsafe { slice::from_raw_parts(array.as_ptr(), 3) };
+        assert_eq!(ptrs[0], array.get(0).unwrap().as_ptr());
+        assert!(ptrs[2].is_null());
+        assert_eq!(array.into_vec().len(), 2);
+    }
+
+    #[test]
+    fn copy_into_field_exact() {
+        let c_str = CStr::from_bytes_with_nul(b"abc\0").unwrap();
+        let mut field = [0xFF; 4];
+        assert_eq!(c_str.copy_into_field(&mut field), Ok(()));
+        assert_eq!(&field, b"abc\0");
+    }
+
+    #[test]
+    fn copy_into_field_too_long() {
+        let c_str = CStr::from_bytes_with_nul(b"abcd\0").unwrap();
+        let mut field = [0xFF; 4];
+        assert_eq!(c_str.copy_into_field(&mut field), Err(FieldTooSmallError { required: 5 }));
+        assert_eq!(field, [0xFF; 4]);
+    }
+
+    #[test]
+    fn copy_into_field_padded() {
+        let c_str = CStr::from_bytes_with_nul(b"ab\0").unwrap();
+        let mut field = [0xFF; 8];
+        assert_eq!(c_str.copy_into_field(&mut field), Ok(()));
+        assert_eq!(&field, b"ab\0\0\0\0\0\0");
+    }
+
+    #[test]
+    fn eq_ignore_ascii_case_c_str() {
+        let reset = CStr::from_bytes_with_nul(b"reset\0").unwrap();
+        let upper = CStr::from_bytes_with_nul(b"Reset\0").unwrap();
+        let longer = CStr::from_bytes_with_nul(b"resets\0").unwrap();
+        assert!(upper.eq_ignore_ascii_case(reset));
+        assert!(!reset.eq_ignore_ascii_case(longer));
+        let high = CStr::from_bytes_with_nul(b"caf\xc3\xa9\0").unwrap();
+        let high_upper = CStr::from_bytes_with_nul(b"CAF\xc3\x89\0").unwrap();
+        assert!(high.eq_ignore_ascii_case(high));
+        assert!(!high.eq_ignore_ascii_case(high_upper));
+    }
+
+    #[test]
+    fn from_u32_hex() {
+        assert_eq!(CString::from_u32_hex(0).as_bytes_with_nul(), b"0\0");
+        assert_eq!(CString::from_u32_hex(0x00C0_FFEE).as_bytes_with_nul(), b"c0ffee\0");
+        assert_eq!(CString::from_u32_hex(u32::max_value()).as_bytes_with_nul(), b"ffffffff\0");
+        assert_eq!(CString::from_u32_hex(0x10).capacity(), 3);
+    }
+
+    #[test]
+    fn from_u64_dec() {
+        assert_eq!(CString::from_u64_dec(0).as_bytes_with_nul(), b"0\0");
+        assert_eq!(CString::from_u64_dec(40_960).as_bytes_with_nul(), b"40960\0");
+        assert_eq!(
+            CString::from_u64_dec(u64::max_value()).as_bytes_with_nul(),
+            b"18446744073709551615\0"
+        );
+    }
+
+    #[test]
+    fn find_byte_c_str() {
+        let c_str = CStr::from_bytes_with_nul(b"+CSQ: 21,0\0").unwrap();
+        assert_eq!(c_str.find_byte(b','), Some(8));
+        assert!(c_str.contains_byte(b','));
+        assert_eq!(c_str.find_byte(b'#'), None);
+        assert!(!c_str.contains_byte(b'#'));
+        assert_eq!(c_str.find_byte(0), None);
+        assert!(!c_str.contains_byte(0));
+    }
+
+    #[test]
+    fn join_c_str() {
+        let parts = [
+            CStr::from_bytes_with_nul(b"usr\0").unwrap(),
+            CStr::from_bytes_with_nul(b"local\0").unwrap(),
+            CStr::from_bytes_with_nul(b"bin\0").unwrap(),
+        ];
+        let path = CString::join(b'/', &parts);
+        assert_eq!(path.as_bytes_with_nul(), b"usr/local/bin\0");
+        assert_eq!(path.capacity(), 14);
+    }
+
+    #[test]
+    fn join_c_str_empty() {
+        let path = CString::join(b'/', &[]);
+        assert_eq!(path.as_bytes_with_nul(), b"\0");
+    }
+
+    #[test]
+    fn to_str_lossy_split_valid() {
+        let c_str = CStr::from_bytes_with_nul("tty\u{2116}1\0".as_bytes()).unwrap();
+        assert_eq!(c_str.to_str_lossy_split(), ("tty\u{2116}1", &b""[..]));
+    }
+
+    #[test]
+    fn to_str_lossy_split_invalid_start() {
+        let c_str = CStr::from_bytes_with_nul(b"\x80tty\0").unwrap();
+        assert_eq!(c_str.to_str_lossy_split(), ("", &b"\x80tty"[..]));
+    }
+
+    #[test]
+    fn to_str_lossy_split_invalid_middle() {
+        let c_str = CStr::from_bytes_with_nul(b"tty\xE2\x84S1\0").unwrap();
+        assert_eq!(c_str.to_str_lossy_split(), ("tty", &b"\xE2\x84S1"[..]));
+    }
+
+    #[test]
+    fn trim_c_str() {
+        let c_str = CStr::from_bytes_with_nul(b"  hi  \0").unwrap();
+        assert_eq!(c_str.trim(b' ').as_bytes_with_nul(), b"hi\0");
+    }
+
+    #[test]
+    fn trim_c_str_all() {
+        let c_str = CStr::from_bytes_with_nul(b"   \0").unwrap();
+        assert_eq!(c_str.trim(b' ').as_bytes_with_nul(), b"\0");
+    }
+
+    #[test]
+    fn as_vec_mut_revalidate() {
+        let mut s = CString::new("serial").unwrap();
+        let vec = unsafe { s.as_vec_mut() };
+        vec.retain(|&b| b != b'i');
+        vec.splice(0..0, b"tty".iter().copied());
+        let ptr = vec.as_ptr();
+        let s = CString::from_vec_retaining(s.into_bytes_with_nul()).unwrap();
+        assert_eq!(s.as_bytes_with_nul(), b"ttyseral\0");
+        assert_eq!(s.as_ptr() as *const u8, ptr);
+    }
+
+    #[test]
+    fn as_vec_mut_interior_nul() {
+        let mut s = CString::new("serial").unwrap();
+        unsafe { s.as_vec_mut()[3] = 0 };
+        let err = CString::from_vec_retaining(s.into_bytes_with_nul()).unwrap_err();
+        assert_eq!(err.nul_position(), 3);
+        assert_eq!(err.into_vec(), b"ser\0al");
+    }
+
+    #[test]
+    fn as_array_shorter() {
+        let c_str = CStr::from_bytes_with_nul(b"ELF1\0").unwrap();
+        let array: Option<&[u8; 2]> = c_str.as_array();
+        assert_eq!(array, Some(b"EL"));
+    }
+
+    #[test]
+    fn as_array_equal() {
+        let c_str = CStr::from_bytes_with_nul(b"ELF1\0").unwrap();
+        let array: Option<&[u8; 4]> = c_str.as_array();
+        assert_eq!(array, Some(b"ELF1"));
+    }
+
+    #[test]
+    fn as_array_longer() {
+        let c_str = CStr::from_bytes_with_nul(b"ELF1\0").unwrap();
+        let array: Option<&[u8; 5]> = c_str.as_array();
+        assert_eq!(array, None);
+    }
 }
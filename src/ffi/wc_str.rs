@@ -0,0 +1,280 @@
+#![allow(clippy::missing_safety_doc)]
+
+use crate::ffi::WCString;
+use alloc::string::FromUtf16Error;
+use core::{
+    char,
+    cmp::Ordering,
+    fmt::{self, Write},
+    slice,
+};
+
+/// Representation of a borrowed wide C string.
+///
+/// This type represents a borrowed reference to a nul-terminated array of
+/// UTF-16 code units. It is the wide counterpart of [`CStr`](crate::ffi::CStr)
+/// and is intended for interoperating with foreign code which expects
+/// nul-terminated UTF-16 strings.
+///
+/// `&WCStr` is to [`WCString`] as `&`[`str`] is to [`String`]: the former in
+/// each pair are borrowed references; the latter are owned strings.
+///
+/// # Examples
+///
+/// ```
+/// use drone_core::ffi::WCStr;
+///
+/// let wide = [0x66, 0x6F, 0x6F, 0];
+/// let wc_str = WCStr::from_wide_with_nul(&wide).expect("WCStr::from_wide_with_nul failed");
+/// assert_eq!(wc_str.to_string().unwrap(), "foo");
+/// ```
+#[allow(clippy::derive_hash_xor_eq)]
+#[derive(Hash)]
+pub struct WCStr {
+    inner: [u16],
+}
+
+/// An error indicating that a nul code unit was not in the expected position.
+///
+/// The slice used to create a [`WCStr`] must have one and only one nul code
+/// unit at the end of the slice.
+///
+/// This error is created by the
+/// [`from_wide_with_nul`](WCStr::from_wide_with_nul) method on [`WCStr`].
+///
+/// # Examples
+///
+/// ```
+/// use drone_core::ffi::{FromWideWithNulError, WCStr};
+///
+/// assert_eq!(
+///     WCStr::from_wide_with_nul(&[0x66, 0, 0x6F, 0]).unwrap_err(),
+///     FromWideWithNulError::InteriorNul { position: 1 },
+/// );
+/// assert_eq!(
+///     WCStr::from_wide_with_nul(&[0x66, 0x6F, 0x6F]).unwrap_err(),
+///     FromWideWithNulError::NotNulTerminated,
+/// );
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FromWideWithNulError {
+    /// Data provided contains an interior nul code unit at `position`.
+    InteriorNul {
+        /// The position of the interior nul code unit.
+        position: usize,
+    },
+    /// Data provided is not nul terminated.
+    NotNulTerminated,
+}
+
+impl WCStr {
+    /// Wraps a raw wide C string with a safe wide C string wrapper.
+    ///
+    /// This function will wrap the provided `ptr` with a `WCStr` wrapper. The
+    /// length of the string is calculated up-front by searching for the nul
+    /// terminator.
+    ///
+    /// # Safety
+    ///
+    /// The same requirements as for [`CStr::from_ptr`](crate::ffi::CStr::from_ptr)
+    /// apply: `ptr` must be valid, point to a nul-terminated sequence of `u16`,
+    /// and the memory must not change while the `WCStr` is alive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drone_core::ffi::WCStr;
+    ///
+    /// let wide = [0x66, 0x6F, 0x6F, 0];
+    /// let wc_str = unsafe { WCStr::from_ptr(wide.as_ptr()) };
+    /// assert_eq!(wc_str.as_wide(), &[0x66, 0x6F, 0x6F]);
+    /// ```
+    pub unsafe fn from_ptr<'a>(ptr: *const u16) -> &'a Self {
+        let len = wcslen(ptr);
+        Self::from_wide_with_nul_unchecked(slice::from_raw_parts(ptr, len + 1))
+    }
+
+    /// Creates a wide C string wrapper from a slice of `u16`.
+    ///
+    /// This function will cast the provided `wide` to a `WCStr` wrapper after
+    /// ensuring that the slice is nul-terminated and does not contain any
+    /// interior nul code units.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drone_core::ffi::WCStr;
+    ///
+    /// assert!(WCStr::from_wide_with_nul(&[0x66, 0]).is_ok());
+    /// assert!(WCStr::from_wide_with_nul(&[0x66]).is_err());
+    /// assert!(WCStr::from_wide_with_nul(&[0, 0x66, 0]).is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `wide` is not nul-terminated or
+    /// contains an interior nul code unit.
+    pub fn from_wide_with_nul(wide: &[u16]) -> Result<&Self, FromWideWithNulError> {
+        let nul_pos = wide.iter().position(|&c| c == 0);
+        if let Some(nul_pos) = nul_pos {
+            if nul_pos + 1 != wide.len() {
+                return Err(FromWideWithNulError::InteriorNul { position: nul_pos });
+            }
+            Ok(unsafe { Self::from_wide_with_nul_unchecked(wide) })
+        } else {
+            Err(FromWideWithNulError::NotNulTerminated)
+        }
+    }
+
+    /// Unsafely creates a wide C string wrapper from a slice of `u16`.
+    ///
+    /// This function will cast the provided `wide` to a `WCStr` wrapper without
+    /// performing any sanity checks. The provided slice **must** be
+    /// nul-terminated and not contain any interior nul code units.
+    #[inline]
+    pub unsafe fn from_wide_with_nul_unchecked(wide: &[u16]) -> &Self {
+        &*(wide as *const [u16] as *const Self)
+    }
+
+    /// Returns the inner pointer to this wide C string.
+    ///
+    /// The returned pointer will be valid for as long as `self` is, and points
+    /// to a contiguous region of memory terminated with a 0 code unit. See
+    /// [`CStr::as_ptr`](crate::ffi::CStr::as_ptr) for the caveats of using the
+    /// returned pointer.
+    #[inline]
+    pub fn as_ptr(&self) -> *const u16 {
+        self.inner.as_ptr()
+    }
+
+    /// Converts this wide C string to a slice of `u16`.
+    ///
+    /// The returned slice will **not** contain the trailing nul terminator that
+    /// this wide C string has.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drone_core::ffi::WCStr;
+    ///
+    /// let wc_str = WCStr::from_wide_with_nul(&[0x66, 0]).unwrap();
+    /// assert_eq!(wc_str.as_wide(), &[0x66]);
+    /// ```
+    #[inline]
+    pub fn as_wide(&self) -> &[u16] {
+        &self.inner[..self.inner.len() - 1]
+    }
+
+    /// Converts this wide C string to a slice of `u16` containing the trailing
+    /// 0 code unit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drone_core::ffi::WCStr;
+    ///
+    /// let wc_str = WCStr::from_wide_with_nul(&[0x66, 0]).unwrap();
+    /// assert_eq!(wc_str.as_wide_with_nul(), &[0x66, 0]);
+    /// ```
+    #[inline]
+    pub fn as_wide_with_nul(&self) -> &[u16] {
+        &self.inner
+    }
+
+    /// Decodes this wide C string into a [`String`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the string contains unpaired
+    /// surrogates.
+    pub fn to_string(&self) -> Result<String, FromUtf16Error> {
+        String::from_utf16(self.as_wide())
+    }
+
+    /// Decodes this wide C string into a [`String`], replacing unpaired
+    /// surrogates with [`U+FFFD REPLACEMENT
+    /// CHARACTER`](core::char::REPLACEMENT_CHARACTER).
+    pub fn to_string_lossy(&self) -> String {
+        String::from_utf16_lossy(self.as_wide())
+    }
+}
+
+impl fmt::Debug for WCStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\"")?;
+        for c in char::decode_utf16(self.as_wide().iter().copied()) {
+            match c {
+                Ok(c) => {
+                    for c in c.escape_debug() {
+                        f.write_char(c)?;
+                    }
+                }
+                Err(err) => write!(f, "\\u{{{:x}}}", err.unpaired_surrogate())?,
+            }
+        }
+        write!(f, "\"")
+    }
+}
+
+impl Default for &WCStr {
+    fn default() -> Self {
+        const SLICE: &[u16] = &[0];
+        unsafe { WCStr::from_wide_with_nul_unchecked(SLICE) }
+    }
+}
+
+impl PartialEq for WCStr {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_wide().eq(other.as_wide())
+    }
+}
+
+impl Eq for WCStr {}
+
+impl PartialOrd for WCStr {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.as_wide().partial_cmp(other.as_wide())
+    }
+}
+
+impl Ord for WCStr {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_wide().cmp(other.as_wide())
+    }
+}
+
+impl ToOwned for WCStr {
+    type Owned = WCString;
+
+    fn to_owned(&self) -> WCString {
+        WCString { inner: self.as_wide_with_nul().into() }
+    }
+}
+
+impl AsRef<WCStr> for WCStr {
+    #[inline]
+    fn as_ref(&self) -> &Self {
+        self
+    }
+}
+
+impl fmt::Display for FromWideWithNulError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FromWideWithNulError::InteriorNul { position } => {
+                write!(f, "data provided contains an interior nul code unit at pos {}", position)
+            }
+            FromWideWithNulError::NotNulTerminated => {
+                write!(f, "data provided is not nul terminated")
+            }
+        }
+    }
+}
+
+unsafe fn wcslen(s: *const u16) -> usize {
+    let mut len = 0;
+    while *s.add(len) != 0 {
+        len += 1;
+    }
+    len
+}
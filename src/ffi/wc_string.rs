@@ -0,0 +1,241 @@
+#![allow(clippy::missing_safety_doc)]
+
+use crate::ffi::WCStr;
+use alloc::borrow::Borrow;
+use core::{fmt, mem, ops, ptr, slice};
+
+/// A type representing an owned, nul-terminated UTF-16 string with no nul code
+/// units in the middle.
+///
+/// This type is the wide counterpart of [`CString`](crate::ffi::CString). It
+/// is useful for interoperating with foreign code which expects nul-terminated
+/// UTF-16 strings.
+///
+/// # Examples
+///
+/// ```
+/// use drone_core::ffi::WCString;
+///
+/// extern "C" fn rom_print(_s: *const u16) {}
+///
+/// let wc_string = WCString::from_str("Hello!").expect("WCString::from_str failed");
+/// unsafe {
+///     rom_print(wc_string.as_ptr());
+/// }
+/// ```
+#[derive(PartialEq, PartialOrd, Eq, Ord, Hash, Clone)]
+pub struct WCString {
+    // Invariant 1: the vector ends with a zero code unit and has a length of at
+    // least one.
+    // Invariant 2: the vector contains only one zero code unit.
+    pub(super) inner: Vec<u16>,
+}
+
+/// An error indicating that an interior nul code unit was found.
+///
+/// This error is created by the [`new`](WCString::new) and
+/// [`from_str`](WCString::from_str) methods on [`WCString`].
+///
+/// # Examples
+///
+/// ```
+/// use drone_core::ffi::{WCString, WNulError};
+///
+/// let _: WNulError = WCString::new(vec![0x66, 0, 0x6F]).unwrap_err();
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct WNulError(usize, Vec<u16>);
+
+impl WCString {
+    /// Creates a new wide C string from a container of `u16`.
+    ///
+    /// This function will consume the provided data and use the underlying
+    /// code units to construct a new string, appending a trailing 0 code unit.
+    /// The provided data should *not* contain any 0 code units in it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drone_core::ffi::WCString;
+    ///
+    /// let wc_string = WCString::new(vec![0x66, 0x6F, 0x6F]).expect("WCString::new failed");
+    /// assert_eq!(wc_string.as_wide_with_nul(), &[0x66, 0x6F, 0x6F, 0]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the supplied data contain an
+    /// internal 0 code unit. The [`WNulError`] returned will contain the data
+    /// as well as the position of the nul code unit.
+    pub fn new<T: Into<Vec<u16>>>(t: T) -> Result<Self, WNulError> {
+        Self::_new(t.into())
+    }
+
+    fn _new(wide: Vec<u16>) -> Result<Self, WNulError> {
+        match wide.iter().position(|&c| c == 0) {
+            Some(i) => Err(WNulError(i, wide)),
+            None => Ok(unsafe { Self::from_vec_unchecked(wide) }),
+        }
+    }
+
+    /// Creates a new wide C string by encoding `s` as UTF-16.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drone_core::ffi::WCString;
+    ///
+    /// let wc_string = WCString::from_str("f\u{1D11E}").expect("WCString::from_str failed");
+    /// assert_eq!(wc_string.as_wide(), &[0x66, 0xD834, 0xDD1E]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `s` contains a nul character.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Result<Self, WNulError> {
+        Self::_new(s.encode_utf16().collect())
+    }
+
+    /// Creates a wide C string by consuming a vector of `u16`, without
+    /// checking for interior 0 code units.
+    pub unsafe fn from_vec_unchecked(mut v: Vec<u16>) -> Self {
+        v.reserve_exact(1);
+        v.push(0);
+        Self { inner: v }
+    }
+
+    /// Retakes ownership of a `WCString` that was transferred to foreign code
+    /// via [`WCString::into_raw`].
+    ///
+    /// # Safety
+    ///
+    /// This should only ever be called with a pointer that was earlier obtained
+    /// by calling [`WCString::into_raw`] on a `WCString`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drone_core::ffi::WCString;
+    ///
+    /// let wc_string = WCString::from_str("foo").expect("WCString::from_str failed");
+    /// let raw = wc_string.into_raw();
+    /// let wc_string = unsafe { WCString::from_raw(raw) };
+    /// assert_eq!(wc_string.to_string().unwrap(), "foo");
+    /// ```
+    pub unsafe fn from_raw(ptr: *mut u16) -> Self {
+        let len = WCStr::from_ptr(ptr).as_wide_with_nul().len();
+        let slice = slice::from_raw_parts_mut(ptr, len);
+        Self { inner: Box::from_raw(slice as *mut [u16]).into_vec() }
+    }
+
+    /// Consumes the `WCString` and transfers ownership of the string to a
+    /// foreign caller.
+    ///
+    /// The pointer which this function returns must be returned to Rust and
+    /// reconstituted using [`WCString::from_raw`] to be properly deallocated.
+    #[inline]
+    pub fn into_raw(self) -> *mut u16 {
+        Box::into_raw(self.into_inner().into_boxed_slice()) as *mut u16
+    }
+
+    /// Consumes the `WCString` and returns the underlying vector of `u16`,
+    /// without the trailing nul terminator.
+    pub fn into_vec(self) -> Vec<u16> {
+        let mut vec = self.into_inner();
+        let nul = vec.pop();
+        debug_assert_eq!(nul, Some(0_u16));
+        vec
+    }
+
+    /// Equivalent to the [`WCString::into_vec`] function except that the
+    /// returned vector includes the trailing nul terminator.
+    pub fn into_vec_with_nul(self) -> Vec<u16> {
+        self.into_inner()
+    }
+
+    /// Extracts a [`WCStr`] slice containing the entire string.
+    #[inline]
+    pub fn as_wc_str(&self) -> &WCStr {
+        &*self
+    }
+
+    /// Bypass "move out of struct which implements [`Drop`] trait" restriction.
+    fn into_inner(self) -> Vec<u16> {
+        let this = mem::ManuallyDrop::new(self);
+        unsafe { ptr::read(&this.inner) }
+    }
+}
+
+impl WNulError {
+    /// Returns the position of the nul code unit in the slice that caused
+    /// [`WCString::new`] to fail.
+    pub fn nul_position(&self) -> usize {
+        self.0
+    }
+
+    /// Consumes this error, returning the underlying vector of `u16` which
+    /// generated the error in the first place.
+    pub fn into_vec(self) -> Vec<u16> {
+        self.1
+    }
+}
+
+// Turns this `WCString` into an empty string to prevent memory unsafe code from
+// working by accident. Inline to prevent LLVM from optimizing it away in debug
+// builds.
+impl Drop for WCString {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { *self.inner.get_unchecked_mut(0) = 0 };
+    }
+}
+
+impl ops::Deref for WCString {
+    type Target = WCStr;
+
+    #[inline]
+    fn deref(&self) -> &WCStr {
+        unsafe { WCStr::from_wide_with_nul_unchecked(&self.inner) }
+    }
+}
+
+impl fmt::Debug for WCString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl Default for WCString {
+    /// Creates an empty `WCString`.
+    fn default() -> Self {
+        let a: &WCStr = Default::default();
+        a.to_owned()
+    }
+}
+
+impl Borrow<WCStr> for WCString {
+    #[inline]
+    fn borrow(&self) -> &WCStr {
+        self
+    }
+}
+
+impl From<&WCStr> for WCString {
+    fn from(s: &WCStr) -> Self {
+        s.to_owned()
+    }
+}
+
+impl AsRef<WCStr> for WCString {
+    #[inline]
+    fn as_ref(&self) -> &WCStr {
+        self
+    }
+}
+
+impl fmt::Display for WNulError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "nul code unit found in provided data at position: {}", self.0)
+    }
+}
@@ -0,0 +1,70 @@
+use crate::{
+    fib::{Fiber, FiberRoot, FiberState},
+    thr::Pend,
+};
+use core::{
+    ops::{Generator, GeneratorState},
+    pin::Pin,
+};
+
+/// Fiber for [`Generator`] pending another thread on each yield.
+///
+/// Can be created with [`fib::new_bridge`](crate::fib::new_bridge).
+pub struct FiberBridge<T, G>
+where
+    T: Pend + 'static,
+    G: Generator,
+{
+    target: &'static T,
+    gen: G,
+}
+
+impl<T, G> Fiber for FiberBridge<T, G>
+where
+    T: Pend + 'static,
+    G: Generator,
+{
+    type Input = ();
+    type Return = G::Return;
+    type Yield = G::Yield;
+
+    fn resume(self: Pin<&mut Self>, (): ()) -> FiberState<G::Yield, G::Return> {
+        let Self { target, gen } = unsafe { self.get_unchecked_mut() };
+        match unsafe { Pin::new_unchecked(gen) }.resume() {
+            GeneratorState::Yielded(value) => {
+                target.pend();
+                FiberState::Yielded(value)
+            }
+            GeneratorState::Complete(value) => FiberState::Complete(value),
+        }
+    }
+}
+
+impl<T, G> FiberRoot for FiberBridge<T, G>
+where
+    T: Pend + 'static,
+    G: Generator<Yield = (), Return = ()>,
+    G: Send + 'static,
+{
+    #[inline]
+    fn advance(self: Pin<&mut Self>) -> bool {
+        match self.resume(()) {
+            FiberState::Yielded(()) => true,
+            FiberState::Complete(()) => false,
+        }
+    }
+}
+
+/// Creates a fiber from the generator `gen`, which pends the thread `target`
+/// on each generator `yield`.
+///
+/// This is useful for producer/consumer setups, where a value produced by a
+/// fiber on one thread should wake a fiber on another thread.
+#[inline]
+pub fn new_bridge<T, G>(target: &'static T, gen: G) -> FiberBridge<T, G>
+where
+    T: Pend + 'static,
+    G: Generator,
+{
+    FiberBridge { target, gen }
+}
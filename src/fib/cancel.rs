@@ -0,0 +1,71 @@
+use crate::fib::{self, FiberGen};
+use alloc::sync::Arc;
+use core::{
+    ops::Generator,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// A handle to request cooperative cancellation of a fiber.
+///
+/// Can be created with [`fib::new_cancellable`](crate::fib::new_cancellable).
+/// The handle is cheap to clone, all clones share the same flag.
+#[derive(Clone, Debug)]
+pub struct CancelToken {
+    flag: Arc<AtomicBool>,
+}
+
+impl CancelToken {
+    /// Requests the fiber to wind down.
+    ///
+    /// The fiber observes the request on its next resume, and is expected to
+    /// run its cleanup code and complete.
+    #[inline]
+    pub fn cancel(&self) {
+        self.flag.store(true, Ordering::Release);
+    }
+
+    /// Returns `true` if the cancellation has been requested.
+    #[inline]
+    pub fn is_cancelled(&self) -> bool {
+        self.flag.load(Ordering::Acquire)
+    }
+}
+
+/// Creates a cancellable fiber from the generator returned by `f`.
+///
+/// The closure `f` receives a [`CancelToken`], which the generator should
+/// check with [`CancelToken::is_cancelled`] after each `yield`. The other
+/// returned token can be used to request the cancellation with
+/// [`CancelToken::cancel`]. Unlike removing the fiber from a chain, this lets
+/// the generator release the resources it owns before completion.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(generators)]
+///
+/// use drone_core::fib::{self, Chain};
+///
+/// let chain = Chain::new();
+/// let (fib, cancel) = fib::new_cancellable(|cancel| {
+///     move || {
+///         while !cancel.is_cancelled() {
+///             yield;
+///         }
+///         // Clean up here.
+///     }
+/// });
+/// chain.add(fib);
+/// assert_eq!(unsafe { chain.drain_once() }, 1);
+/// cancel.cancel();
+/// assert_eq!(unsafe { chain.drain_once() }, 0);
+/// ```
+#[inline]
+pub fn new_cancellable<F, G>(f: F) -> (FiberGen<G>, CancelToken)
+where
+    F: FnOnce(CancelToken) -> G,
+    G: Generator,
+{
+    let token = CancelToken { flag: Arc::new(AtomicBool::new(false)) };
+    (fib::new(f(token.clone())), token)
+}
@@ -1,18 +1,52 @@
 use crate::fib::FiberRoot;
+use alloc::sync::Arc;
 use core::{
+    any::Any,
     pin::Pin,
     ptr,
-    sync::atomic::{AtomicPtr, Ordering},
+    sync::atomic::{AtomicBool, AtomicPtr, Ordering},
 };
 
 /// A lock-free stack of fibers.
+///
+/// The chain is a singly linked list. Each added fiber is allocated in its own
+/// node, which is freed when the fiber completes or is removed. The chain never
+/// reallocates, so there is no capacity to reserve up front. [`Chain::new`] is
+/// a `const fn` and doesn't allocate.
+///
+/// The chain is a single word. A selectable drain order, fiber priorities,
+/// suspension, and a yield observer are provided by
+/// [`SchedChain`](super::SchedChain) instead.
 pub struct Chain {
-    head: AtomicPtr<Node>,
+    pub(super) head: AtomicPtr<Node>,
 }
 
-struct Node {
-    fib: Pin<Box<dyn FiberRoot>>,
-    next: *mut Node,
+/// A handle to a fiber added with [`Chain::add_with_handle`].
+///
+/// The fiber can be detached from the chain with [`FiberHandle::cancel`].
+pub struct FiberHandle {
+    live: Arc<AtomicBool>,
+}
+
+struct Handled<F: FiberRoot> {
+    fib: F,
+    live: Arc<AtomicBool>,
+}
+
+// A fiber stored in the chain.
+pub(super) trait Root: Send + 'static {
+    fn advance_root(self: Pin<&mut Self>) -> bool;
+
+    fn advance_observed(self: Pin<&mut Self>, observer: &mut dyn FnMut(&dyn Any)) -> bool;
+}
+
+// The priority of fibers added without an explicit priority.
+pub(super) const BASE_PRIORITY: u8 = 0;
+
+pub(super) struct Node {
+    fib: Pin<Box<dyn Root>>,
+    pub(super) priority: u8,
+    pub(super) next: *mut Node,
 }
 
 impl Chain {
@@ -23,7 +57,15 @@ impl Chain {
 
     /// Adds the fiber `fib` first to the chain.
     pub fn add<F: FiberRoot>(&self, fib: F) {
-        self.push(Node::new(fib));
+        self.push(Node::new(fib, BASE_PRIORITY));
+    }
+
+    /// Adds the fiber `fib` first to the chain, returning a handle to cancel
+    /// the fiber.
+    pub fn add_with_handle<F: FiberRoot>(&self, fib: F) -> FiberHandle {
+        let live = Arc::new(AtomicBool::new(true));
+        self.add(Handled { fib, live: Arc::clone(&live) });
+        FiberHandle { live }
     }
 
     /// Returns `true` if the chain contains no fibers.
@@ -31,6 +73,25 @@ impl Chain {
         self.head.load(Ordering::Acquire).is_null()
     }
 
+    /// Returns the number of fibers in the chain.
+    ///
+    /// Completed fibers are removed by [`Chain::drain`], so only live fibers
+    /// are counted.
+    ///
+    /// # Safety
+    ///
+    /// This method walks the whole chain, so it must not be called while the
+    /// chain can be drained concurrently, e.g. by a preempting thread.
+    pub unsafe fn len(&self) -> usize {
+        let mut len = 0;
+        let mut curr = self.head.load(Ordering::Acquire);
+        while !curr.is_null() {
+            len += 1;
+            curr = (*curr).next;
+        }
+        len
+    }
+
     /// Advances fibers, removing completed ones.
     ///
     /// # Safety
@@ -38,11 +99,58 @@ impl Chain {
     /// This method is not reentrant.
     #[inline(never)]
     pub unsafe fn drain(&self) {
-        let mut prev = ptr::null_mut();
-        let mut curr = self.head.load(Ordering::Acquire);
-        while !curr.is_null() {
+        self.drain_sequential();
+    }
+
+    /// Advances every fiber exactly once, removing completed ones. Returns the
+    /// number of remaining fibers.
+    ///
+    /// This is meant for driving fibers from ordinary code, e.g. in unit tests.
+    ///
+    /// # Safety
+    ///
+    /// This method is not reentrant, and must not be called while a thread
+    /// owning the chain can preempt the caller.
+    pub unsafe fn drain_once(&self) -> usize {
+        self.drain_sequential();
+        self.len()
+    }
+
+    pub(super) unsafe fn drain_sequential(&self) {
+        self.advance_until(
+            ptr::null_mut(),
+            self.head.load(Ordering::Acquire),
+            ptr::null_mut(),
+            |_| true,
+            None,
+        );
+    }
+
+    // Advances fibers from `curr` up to `stop`, where `prev` precedes `curr`,
+    // skipping fibers which priorities don't match `filter`, and reporting
+    // yields to `observer`. Returns the first fiber left in the chain, or
+    // `stop` if there is none.
+    pub(super) unsafe fn advance_until(
+        &self,
+        mut prev: *mut Node,
+        mut curr: *mut Node,
+        stop: *mut Node,
+        filter: impl Fn(u8) -> bool,
+        mut observer: Option<&mut dyn FnMut(&dyn Any)>,
+    ) -> *mut Node {
+        let mut first = stop;
+        while curr != stop {
             let next = (*curr).next;
-            if (*curr).fib.as_mut().advance() {
+            if !filter((*curr).priority) || {
+                let fib = (*curr).fib.as_mut();
+                match observer {
+                    Some(ref mut observer) => fib.advance_observed(&mut **observer),
+                    None => fib.advance_root(),
+                }
+            } {
+                if first == stop {
+                    first = curr;
+                }
                 prev = curr;
             } else {
                 if prev.is_null() {
@@ -65,9 +173,10 @@ impl Chain {
             }
             curr = next;
         }
+        first
     }
 
-    fn push(&self, node: Node) {
+    pub(super) fn push(&self, node: Node) {
         let node = Box::into_raw(Box::new(node));
         loop {
             let head = self.head.load(Ordering::Relaxed);
@@ -79,8 +188,49 @@ impl Chain {
     }
 }
 
+impl FiberHandle {
+    /// Removes the fiber from its chain.
+    ///
+    /// The fiber will not be resumed anymore, and will be dropped on the next
+    /// [`Chain::drain`]. Returns `false` if the fiber has already completed or
+    /// has already been removed.
+    pub fn cancel(&self) -> bool {
+        self.live.swap(false, Ordering::AcqRel)
+    }
+}
+
+impl<F: FiberRoot> FiberRoot for Handled<F> {
+    fn advance(self: Pin<&mut Self>) -> bool {
+        let Self { fib, live } = unsafe { self.get_unchecked_mut() };
+        if !live.load(Ordering::Acquire) {
+            return false;
+        }
+        let fib = unsafe { Pin::new_unchecked(fib) };
+        if fib.advance() {
+            true
+        } else {
+            live.store(false, Ordering::Release);
+            false
+        }
+    }
+}
+
+impl<F: FiberRoot> Root for F {
+    fn advance_root(self: Pin<&mut Self>) -> bool {
+        self.advance()
+    }
+
+    fn advance_observed(self: Pin<&mut Self>, observer: &mut dyn FnMut(&dyn Any)) -> bool {
+        let alive = self.advance();
+        if alive {
+            observer(&());
+        }
+        alive
+    }
+}
+
 impl Node {
-    fn new<F: FiberRoot>(fib: F) -> Self {
-        Self { fib: Box::pin(fib), next: ptr::null_mut() }
+    pub(super) fn new<F: Root>(fib: F, priority: u8) -> Self {
+        Self { fib: Box::pin(fib), priority, next: ptr::null_mut() }
     }
 }
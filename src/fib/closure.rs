@@ -19,6 +19,38 @@ where
     F: FnOnce() -> R,
     F: Unpin;
 
+/// Fiber for [`FnMut`] closure, which completes after a fixed number of
+/// invocations.
+///
+/// Can be created with [`fib::new_countdown`](crate::fib::new_countdown).
+pub struct FiberCountdown<F>
+where
+    F: FnMut(),
+{
+    f: Option<F>,
+    count: usize,
+}
+
+/// Fiber for [`FnMut`] closure, which is called on every `n`th invocation.
+///
+/// Can be created with [`fib::new_every`](crate::fib::new_every).
+pub struct FiberEvery<F>
+where
+    F: FnMut(),
+{
+    f: F,
+    n: usize,
+    tick: usize,
+}
+
+/// Fiber for [`FnMut`] predicate, which completes once the predicate returns
+/// `true`.
+///
+/// Can be created with [`fib::new_until`](crate::fib::new_until).
+pub struct FiberUntil<P>(Option<P>)
+where
+    P: FnMut() -> bool;
+
 #[marker]
 pub trait ReturnNone: Send + 'static {}
 
@@ -94,6 +126,118 @@ where
     }
 }
 
+impl<F> Fiber for FiberCountdown<F>
+where
+    F: FnMut(),
+{
+    type Input = ();
+    type Return = ();
+    type Yield = ();
+
+    fn resume(self: Pin<&mut Self>, (): ()) -> FiberState<(), ()> {
+        let Self { f: option, count } = unsafe { self.get_unchecked_mut() };
+        match option {
+            Some(f) => {
+                if *count > 0 {
+                    f();
+                    *count -= 1;
+                }
+                if *count > 0 {
+                    FiberState::Yielded(())
+                } else {
+                    *option = None;
+                    FiberState::Complete(())
+                }
+            }
+            None => panic!("fiber resumed after completion"),
+        }
+    }
+}
+
+impl<F> FiberRoot for FiberCountdown<F>
+where
+    F: FnMut(),
+    F: Send + 'static,
+{
+    #[inline]
+    fn advance(self: Pin<&mut Self>) -> bool {
+        match self.resume(()) {
+            FiberState::Yielded(()) => true,
+            FiberState::Complete(()) => false,
+        }
+    }
+}
+
+impl<P> Fiber for FiberUntil<P>
+where
+    P: FnMut() -> bool,
+{
+    type Input = ();
+    type Return = ();
+    type Yield = ();
+
+    fn resume(self: Pin<&mut Self>, (): ()) -> FiberState<(), ()> {
+        let option = unsafe { &mut self.get_unchecked_mut().0 };
+        match option {
+            Some(pred) => {
+                if pred() {
+                    *option = None;
+                    FiberState::Complete(())
+                } else {
+                    FiberState::Yielded(())
+                }
+            }
+            None => panic!("fiber resumed after completion"),
+        }
+    }
+}
+
+impl<P> FiberRoot for FiberUntil<P>
+where
+    P: FnMut() -> bool,
+    P: Send + 'static,
+{
+    #[inline]
+    fn advance(self: Pin<&mut Self>) -> bool {
+        match self.resume(()) {
+            FiberState::Yielded(()) => true,
+            FiberState::Complete(()) => false,
+        }
+    }
+}
+
+impl<F> Fiber for FiberEvery<F>
+where
+    F: FnMut(),
+{
+    type Input = ();
+    type Return = !;
+    type Yield = ();
+
+    fn resume(self: Pin<&mut Self>, (): ()) -> FiberState<(), !> {
+        let Self { f, n, tick } = unsafe { self.get_unchecked_mut() };
+        *tick += 1;
+        if *tick >= *n {
+            *tick = 0;
+            f();
+        }
+        FiberState::Yielded(())
+    }
+}
+
+impl<F> FiberRoot for FiberEvery<F>
+where
+    F: FnMut(),
+    F: Send + 'static,
+{
+    #[inline]
+    fn advance(self: Pin<&mut Self>) -> bool {
+        match self.resume(()) {
+            FiberState::Yielded(()) => true,
+        }
+    }
+}
+
 /// Creates a fiber from the closure `f`.
 #[inline]
 pub fn new_fn<F, Y, R>(f: F) -> FiberFn<F, Y, R>
@@ -106,7 +250,8 @@ where
 /// Creates a fiber from the closure `f`.
 ///
 /// This type of fiber will never yield and will busy its thread until
-/// completion.
+/// completion. The closure runs exactly once on the next thread invocation,
+/// after which the fiber is removed from the chain.
 #[inline]
 pub fn new_once<F, R>(f: F) -> FiberOnce<F, R>
 where
@@ -116,6 +261,54 @@ where
     FiberOnce(Some(f))
 }
 
+/// Creates a fiber from the closure `f`, which completes after `count`
+/// invocations.
+///
+/// The closure is called once on each resume, and the fiber yields between
+/// the calls. If `count` is `0`, the fiber completes on the first resume
+/// without calling the closure.
+#[inline]
+pub fn new_countdown<F>(count: usize, f: F) -> FiberCountdown<F>
+where
+    F: FnMut(),
+{
+    FiberCountdown { f: Some(f), count }
+}
+
+/// Creates a fiber from the closure `f`, which is called on every `n`th
+/// resume.
+///
+/// The fiber keeps a tick counter, and calls the closure when the counter
+/// reaches `n`, then resets the counter. The other resumes only increment the
+/// counter. If `n` is `1`, the closure is called on each resume. The fiber
+/// never completes.
+///
+/// # Panics
+///
+/// If `n` is `0` (only in debug builds).
+#[inline]
+pub fn new_every<F>(n: usize, f: F) -> FiberEvery<F>
+where
+    F: FnMut(),
+{
+    debug_assert!(n > 0, "fiber period must be non-zero");
+    FiberEvery { f, n, tick: 0 }
+}
+
+/// Creates a fiber from the predicate `pred`, which completes once the
+/// predicate returns `true`.
+///
+/// The predicate is called once on each resume, and the fiber yields while it
+/// returns `false`. This is useful for waiting on a hardware flag from an
+/// interrupt-driven thread without busy-looping.
+#[inline]
+pub fn new_until<P>(pred: P) -> FiberUntil<P>
+where
+    P: FnMut() -> bool,
+{
+    FiberUntil(Some(pred))
+}
+
 /// Extends [`ThrToken`](crate::thr::ThrToken) types with `add_fn` and
 /// `add_once` methods.
 pub trait ThrFiberClosure: ThrToken {
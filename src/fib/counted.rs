@@ -0,0 +1,89 @@
+use crate::fib::{Fiber, FiberRoot, FiberState};
+use alloc::sync::Arc;
+use core::{
+    pin::Pin,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Fiber counting how many times another fiber has been polled.
+///
+/// Can be created with [`fib::new_counted`](crate::fib::new_counted).
+pub struct FiberCounted<Fb> {
+    fib: Fb,
+    count: Arc<AtomicUsize>,
+}
+
+/// A handle to the poll count of a
+/// [`FiberCounted`](crate::fib::FiberCounted).
+#[derive(Clone, Debug)]
+pub struct PollCount {
+    count: Arc<AtomicUsize>,
+}
+
+impl<Fb> FiberCounted<Fb> {
+    fn tick(&self) {
+        // The fiber is the only writer, so a separate load and store is enough.
+        let count = self.count.load(Ordering::Relaxed);
+        self.count.store(count.saturating_add(1), Ordering::Relaxed);
+    }
+}
+
+impl<Fb: Fiber> Fiber for FiberCounted<Fb> {
+    type Input = Fb::Input;
+    type Return = Fb::Return;
+    type Yield = Fb::Yield;
+
+    #[inline]
+    fn resume(self: Pin<&mut Self>, input: Fb::Input) -> FiberState<Fb::Yield, Fb::Return> {
+        let this = unsafe { self.get_unchecked_mut() };
+        this.tick();
+        unsafe { Pin::new_unchecked(&mut this.fib) }.resume(input)
+    }
+}
+
+impl<Fb: FiberRoot> FiberRoot for FiberCounted<Fb> {
+    #[inline]
+    fn advance(self: Pin<&mut Self>) -> bool {
+        let this = unsafe { self.get_unchecked_mut() };
+        this.tick();
+        unsafe { Pin::new_unchecked(&mut this.fib) }.advance()
+    }
+}
+
+impl PollCount {
+    /// Returns the number of times the fiber has been polled.
+    ///
+    /// The count saturates at [`usize::MAX`](core::usize::MAX).
+    #[inline]
+    pub fn poll_count(&self) -> usize {
+        self.count.load(Ordering::Relaxed)
+    }
+}
+
+/// Wraps the fiber `fib` to count how many times it is polled.
+///
+/// The count is available through the returned
+/// [`PollCount`](crate::fib::PollCount) handle, which is useful for finding
+/// fibers that take the most of a thread time.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(generators)]
+///
+/// use drone_core::fib::{self, Chain};
+///
+/// let chain = Chain::new();
+/// let (fib, count) = fib::new_counted(fib::new(|| {
+///     yield;
+/// }));
+/// chain.add(fib);
+/// assert_eq!(unsafe { chain.drain_once() }, 1);
+/// assert_eq!(unsafe { chain.drain_once() }, 0);
+/// assert_eq!(count.poll_count(), 2);
+/// ```
+#[inline]
+pub fn new_counted<Fb>(fib: Fb) -> (FiberCounted<Fb>, PollCount) {
+    let count = Arc::new(AtomicUsize::new(0));
+    (FiberCounted { fib, count: Arc::clone(&count) }, PollCount { count })
+}
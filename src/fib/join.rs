@@ -0,0 +1,97 @@
+use crate::fib::{Fiber, FiberState};
+use core::{mem, pin::Pin};
+
+/// Fiber waiting for two fibers.
+///
+/// Can be created with [`fib::join`](crate::fib::join).
+pub struct FiberJoin<A, B>
+where
+    A: Fiber<Input = (), Yield = ()>,
+    B: Fiber<Input = (), Yield = ()>,
+{
+    a: Joined<A>,
+    b: Joined<B>,
+}
+
+enum Joined<F: Fiber> {
+    Running(F),
+    Complete(F::Return),
+    Taken,
+}
+
+impl<A, B> Fiber for FiberJoin<A, B>
+where
+    A: Fiber<Input = (), Yield = ()>,
+    B: Fiber<Input = (), Yield = ()>,
+{
+    type Input = ();
+    type Return = (A::Return, B::Return);
+    type Yield = ();
+
+    fn resume(self: Pin<&mut Self>, (): ()) -> FiberState<(), Self::Return> {
+        let Self { a, b } = unsafe { self.get_unchecked_mut() };
+        let a_complete = unsafe { Pin::new_unchecked(&mut *a) }.advance();
+        let b_complete = unsafe { Pin::new_unchecked(&mut *b) }.advance();
+        if a_complete && b_complete {
+            FiberState::Complete((a.take(), b.take()))
+        } else {
+            FiberState::Yielded(())
+        }
+    }
+}
+
+impl<F: Fiber<Input = (), Yield = ()>> Joined<F> {
+    // Resumes the fiber if it is still running. Returns `true` if the fiber
+    // has completed.
+    fn advance(self: Pin<&mut Self>) -> bool {
+        let this = unsafe { self.get_unchecked_mut() };
+        match this {
+            Self::Running(fib) => {
+                if let FiberState::Complete(value) = unsafe { Pin::new_unchecked(fib) }.resume(()) {
+                    *this = Self::Complete(value);
+                    true
+                } else {
+                    false
+                }
+            }
+            Self::Complete(_) => true,
+            Self::Taken => panic!("fiber resumed after completion"),
+        }
+    }
+
+    fn take(&mut self) -> F::Return {
+        match mem::replace(self, Self::Taken) {
+            Self::Complete(value) => value,
+            Self::Running(_) | Self::Taken => unreachable!(),
+        }
+    }
+}
+
+/// Creates a fiber, which resumes both fibers `a` and `b` on each resume, and
+/// completes when both of them complete.
+///
+/// The fiber returns a tuple of the return values of `a` and `b`. A completed
+/// fiber is dropped and isn't resumed anymore, while its return value is kept
+/// until the other fiber completes. The fiber is [`Send`] if both `a` and `b`
+/// and their return values are.
+///
+/// # Examples
+///
+/// ```
+/// use core::pin::Pin;
+/// use drone_core::fib::{self, Fiber, FiberState};
+///
+/// let mut fib = fib::join(
+///     fib::new_fn(|| FiberState::<(), _>::Complete(1)),
+///     fib::new_fn(|| FiberState::<(), _>::Complete(2)),
+/// );
+/// assert_eq!(Pin::new(&mut fib).resume(()), FiberState::Complete((1, 2)));
+/// ```
+#[inline]
+pub fn join<A, B>(a: A, b: B) -> FiberJoin<A, B>
+where
+    A: Fiber<Input = (), Yield = ()>,
+    B: Fiber<Input = (), Yield = ()>,
+{
+    FiberJoin { a: Joined::Running(a), b: Joined::Running(b) }
+}
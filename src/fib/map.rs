@@ -0,0 +1,153 @@
+use crate::fib::{Fiber, FiberRoot, FiberState, FiberThen, FiberTimeout};
+use core::pin::Pin;
+
+/// Fiber transforming the values yielded by another fiber.
+///
+/// Can be created with [`FiberExt::map`](crate::fib::FiberExt::map).
+pub struct FiberMap<Fb, F> {
+    fib: Fb,
+    f: F,
+}
+
+/// Extends [`Fiber`] types with combinator methods.
+pub trait FiberExt: Fiber + Sized {
+    /// Wraps this fiber to apply `f` to each yielded value. The return value
+    /// is passed through unchanged.
+    ///
+    /// If the resulting fiber yields `()` and returns `()`, it can be added to
+    /// a fiber chain. In that case `f` must be [`Send`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(generators)]
+    ///
+    /// use core::pin::Pin;
+    /// use drone_core::fib::{self, Fiber, FiberExt, FiberState};
+    ///
+    /// let mut fib = fib::new(|| {
+    ///     yield 100;
+    ///     3
+    /// })
+    /// .map(|counts| counts * 2);
+    /// let mut fib = unsafe { Pin::new_unchecked(&mut fib) };
+    /// assert_eq!(fib.as_mut().resume(()), FiberState::Yielded(200));
+    /// assert_eq!(fib.as_mut().resume(()), FiberState::Complete(3));
+    /// ```
+    #[inline]
+    fn map<F, U>(self, f: F) -> FiberMap<Self, F>
+    where
+        F: FnMut(Self::Yield) -> U,
+    {
+        FiberMap { fib: self, f }
+    }
+
+    /// Wraps this fiber to give up after `polls` resumptions. If this fiber
+    /// hasn't completed by then, it is dropped, and the resulting fiber
+    /// completes with [`TimedOut`](crate::fib::TimedOut).
+    ///
+    /// This is useful as a timeout on a thread resumed at a fixed rate. If this
+    /// fiber completes exactly at the last allowed resumption, its return value
+    /// wins.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(generators)]
+    ///
+    /// use core::pin::Pin;
+    /// use drone_core::fib::{self, Fiber, FiberExt, FiberState, TimedOut};
+    ///
+    /// let mut fib = fib::new(|| {
+    ///     loop {
+    ///         yield;
+    ///     }
+    /// })
+    /// .timeout_polls(2);
+    /// let mut fib = unsafe { Pin::new_unchecked(&mut fib) };
+    /// assert_eq!(fib.as_mut().resume(()), FiberState::Yielded(()));
+    /// assert_eq!(fib.as_mut().resume(()), FiberState::Complete(Err(TimedOut)));
+    /// ```
+    #[inline]
+    fn timeout_polls(self, polls: usize) -> FiberTimeout<Self> {
+        FiberTimeout::new(self, polls)
+    }
+
+    /// Wraps this fiber to continue with another fiber once it completes.
+    ///
+    /// The return value of this fiber is passed to `next`, which builds the
+    /// follow-up fiber. The follow-up fiber is resumed right away, in the same
+    /// resumption, and then takes the place of this fiber. This gives a
+    /// sequential pipeline of fibers, which can occupy a single slot in a
+    /// fiber chain.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(generators)]
+    ///
+    /// use core::pin::Pin;
+    /// use drone_core::fib::{self, Fiber, FiberExt, FiberState};
+    ///
+    /// let mut fib = fib::new(|| {
+    ///     yield 1;
+    ///     10
+    /// })
+    /// .then(|base| {
+    ///     fib::new(move || {
+    ///         yield base + 1;
+    ///         base + 2
+    ///     })
+    /// });
+    /// let mut fib = unsafe { Pin::new_unchecked(&mut fib) };
+    /// assert_eq!(fib.as_mut().resume(()), FiberState::Yielded(1));
+    /// assert_eq!(fib.as_mut().resume(()), FiberState::Yielded(11));
+    /// assert_eq!(fib.as_mut().resume(()), FiberState::Complete(12));
+    /// ```
+    #[inline]
+    fn then<F, G>(self, next: F) -> FiberThen<Self, F, G>
+    where
+        Self: Fiber<Input = ()>,
+        F: FnOnce(Self::Return) -> G,
+        G: Fiber<Input = (), Yield = Self::Yield>,
+    {
+        FiberThen::new(self, next)
+    }
+}
+
+impl<Fb: Fiber> FiberExt for Fb {}
+
+impl<Fb, F, U> Fiber for FiberMap<Fb, F>
+where
+    Fb: Fiber,
+    F: FnMut(Fb::Yield) -> U,
+{
+    type Input = Fb::Input;
+    type Return = Fb::Return;
+    type Yield = U;
+
+    #[inline]
+    fn resume(self: Pin<&mut Self>, input: Fb::Input) -> FiberState<U, Fb::Return> {
+        let Self { fib, f } = unsafe { self.get_unchecked_mut() };
+        match unsafe { Pin::new_unchecked(fib) }.resume(input) {
+            FiberState::Yielded(value) => FiberState::Yielded(f(value)),
+            FiberState::Complete(value) => FiberState::Complete(value),
+        }
+    }
+}
+
+impl<Fb, F> FiberRoot for FiberMap<Fb, F>
+where
+    Fb: Fiber<Input = (), Return = ()>,
+    Fb: Send + 'static,
+    F: FnMut(Fb::Yield),
+    F: Send + 'static,
+{
+    #[inline]
+    fn advance(self: Pin<&mut Self>) -> bool {
+        match self.resume(()) {
+            FiberState::Yielded(()) => true,
+            FiberState::Complete(()) => false,
+        }
+    }
+}
@@ -13,8 +13,8 @@
 //!
 //! # Basic Fibers
 //!
-//! A basic fiber can be created with [`fib::new`], [`fib::new_fn`], or
-//! [`fib::new_once`]:
+//! A basic fiber can be created with [`fib::new`], [`fib::new_fn`],
+//! [`fib::new_once`], or [`fib::new_future`]:
 //!
 //! ```
 //! # #![feature(generators)]
@@ -50,6 +50,18 @@
 //!     // do some work and immediately return
 //!     4
 //! });
+//!
+//! # struct Thr;
+//! # impl drone_core::thr::Pend for Thr {
+//! #     fn pend(&self) {}
+//! # }
+//! # static THR: Thr = Thr;
+//! // A fiber based on a `Future`, which pends the thread `THR` when woken.
+//! // This is `impl Fiber<Input = (), Yield = (), Return = i32>`
+//! let d = fib::new_future(&THR, async {
+//!     // do some work and return
+//!     5
+//! });
 //! ```
 //!
 //! A basic fiber can be attached to a thread with
@@ -57,7 +69,9 @@
 //! [`token.add_fn(...)`](fib::ThrFiberClosure::add_fn), or
 //! [`token.add_once(...)`](fib::ThrFiberClosure::add_once). Note that fibers
 //! that are directly attached to threads can't have yield and return values
-//! other than `()` or `!`.
+//! other than `()` or `!`. A generator with arbitrary yield and return values
+//! can be wrapped with [`fib::new_generator`], which reports them through a
+//! separate handle.
 //!
 //! ```
 //! # #![feature(generators)]
@@ -164,20 +178,45 @@
 //! # }
 //! ```
 
+mod bridge;
+mod cancel;
 mod chain;
 mod closure;
+mod counted;
 mod future;
 mod generator;
+mod join;
+mod map;
+mod poll;
+mod progress;
+mod sched_chain;
+mod select;
 mod stream_pulse;
 mod stream_ring;
+mod then;
+mod timeout;
 
 pub use self::{
-    chain::Chain,
-    closure::{new_fn, new_once, FiberFn, FiberOnce, ThrFiberClosure},
+    bridge::{new_bridge, FiberBridge},
+    cancel::{new_cancellable, CancelToken},
+    chain::{Chain, FiberHandle},
+    closure::{
+        new_countdown, new_every, new_fn, new_once, new_until, FiberCountdown, FiberEvery, FiberFn,
+        FiberOnce, FiberUntil, ThrFiberClosure,
+    },
+    counted::{new_counted, FiberCounted, PollCount},
     future::{FiberFuture, ThrFiberFuture},
     generator::{new, FiberGen, ThrFiberGen},
+    join::{join, FiberJoin},
+    map::{FiberExt, FiberMap},
+    poll::{new_future, FiberPoll},
+    progress::{new_generator, FiberProgress, Progress},
+    sched_chain::{DrainPolicy, SchedChain, SuspendGuard},
+    select::{select, FiberSelect, Selected},
     stream_pulse::{FiberStreamPulse, ThrFiberStreamPulse, TryFiberStreamPulse},
     stream_ring::{FiberStreamRing, ThrFiberStreamRing, TryFiberStreamRing},
+    then::FiberThen,
+    timeout::{FiberTimeout, TimedOut},
 };
 pub use FiberState::*;
 
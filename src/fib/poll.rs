@@ -0,0 +1,95 @@
+use crate::{
+    fib::{Fiber, FiberRoot, FiberState},
+    thr::Pend,
+};
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+};
+
+/// Fiber for [`Future`].
+///
+/// Can be created with [`fib::new_future`](crate::fib::new_future).
+pub struct FiberPoll<T, F>
+where
+    T: Pend + 'static,
+    F: Future,
+{
+    thr: &'static T,
+    fut: F,
+    complete: bool,
+}
+
+impl<T, F> Fiber for FiberPoll<T, F>
+where
+    T: Pend + 'static,
+    F: Future,
+{
+    type Input = ();
+    type Return = F::Output;
+    type Yield = ();
+
+    fn resume(self: Pin<&mut Self>, (): ()) -> FiberState<(), F::Output> {
+        let this = unsafe { self.get_unchecked_mut() };
+        if this.complete {
+            panic!("fiber resumed after completion");
+        }
+        let waker = waker(this.thr);
+        let mut cx = Context::from_waker(&waker);
+        match unsafe { Pin::new_unchecked(&mut this.fut) }.poll(&mut cx) {
+            Poll::Pending => FiberState::Yielded(()),
+            Poll::Ready(output) => {
+                this.complete = true;
+                FiberState::Complete(output)
+            }
+        }
+    }
+}
+
+impl<T, F> FiberRoot for FiberPoll<T, F>
+where
+    T: Pend + 'static,
+    F: Future<Output = ()>,
+    F: Send + 'static,
+{
+    #[inline]
+    fn advance(self: Pin<&mut Self>) -> bool {
+        match self.resume(()) {
+            FiberState::Yielded(()) => true,
+            FiberState::Complete(()) => false,
+        }
+    }
+}
+
+/// Creates a fiber from the future `fut`, which runs on the thread `thr`.
+///
+/// This type of fiber polls the future on each resume and yields while the
+/// future is pending. The future is polled with a waker, which pends the
+/// thread `thr`, so the fiber is resumed again when the future is woken. The
+/// fiber should be attached to the same thread.
+///
+/// The output of the future can be retrieved with
+/// [`token.add_future(...)`](crate::fib::ThrFiberFuture::add_future).
+#[inline]
+pub fn new_future<T, F>(thr: &'static T, fut: F) -> FiberPoll<T, F>
+where
+    T: Pend + 'static,
+    F: Future,
+{
+    FiberPoll { thr, fut, complete: false }
+}
+
+fn waker<T: Pend + 'static>(thr: &'static T) -> Waker {
+    unsafe fn clone<T: Pend + 'static>(thr: *const ()) -> RawWaker {
+        RawWaker::new(thr, vtable::<T>())
+    }
+    unsafe fn wake<T: Pend + 'static>(thr: *const ()) {
+        (*(thr as *const T)).pend();
+    }
+    unsafe fn drop(_thr: *const ()) {}
+    fn vtable<T: Pend + 'static>() -> &'static RawWakerVTable {
+        &RawWakerVTable::new(clone::<T>, wake::<T>, wake::<T>, drop)
+    }
+    unsafe { Waker::from_raw(RawWaker::new(thr as *const T as *const (), vtable::<T>())) }
+}
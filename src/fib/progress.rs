@@ -0,0 +1,141 @@
+use crate::fib::{Fiber, FiberRoot, FiberState};
+use alloc::sync::Arc;
+use core::{
+    marker::PhantomData,
+    ops::{Generator, GeneratorState},
+    pin::Pin,
+    ptr,
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+/// Fiber for [`Generator`] reporting its progress.
+///
+/// Can be created with [`fib::new_generator`](crate::fib::new_generator).
+pub struct FiberProgress<G>
+where
+    G: Generator,
+{
+    gen: G,
+    slot: Arc<Slot<G::Yield, G::Return>>,
+}
+
+/// A handle to the latest state reported by a
+/// [`FiberProgress`](crate::fib::FiberProgress).
+pub struct Progress<Y, R> {
+    slot: Arc<Slot<Y, R>>,
+}
+
+struct Slot<Y, R> {
+    ptr: AtomicPtr<FiberState<Y, R>>,
+    _marker: PhantomData<Box<FiberState<Y, R>>>,
+}
+
+unsafe impl<Y: Send, R: Send> Sync for Slot<Y, R> {}
+
+impl<G> Fiber for FiberProgress<G>
+where
+    G: Generator,
+{
+    type Input = ();
+    type Return = ();
+    type Yield = ();
+
+    fn resume(self: Pin<&mut Self>, (): ()) -> FiberState<(), ()> {
+        let this = unsafe { self.get_unchecked_mut() };
+        match unsafe { Pin::new_unchecked(&mut this.gen) }.resume() {
+            GeneratorState::Yielded(value) => {
+                this.slot.put(FiberState::Yielded(value));
+                FiberState::Yielded(())
+            }
+            GeneratorState::Complete(value) => {
+                this.slot.put(FiberState::Complete(value));
+                FiberState::Complete(())
+            }
+        }
+    }
+}
+
+impl<G> FiberRoot for FiberProgress<G>
+where
+    G: Generator,
+    G: Send + 'static,
+    G::Yield: Send,
+    G::Return: Send,
+{
+    #[inline]
+    fn advance(self: Pin<&mut Self>) -> bool {
+        match self.resume(()) {
+            FiberState::Yielded(()) => true,
+            FiberState::Complete(()) => false,
+        }
+    }
+}
+
+impl<Y, R> Progress<Y, R> {
+    /// Takes the latest state reported by the fiber.
+    ///
+    /// Returns `None` if the fiber hasn't been resumed since the last call.
+    /// Only the latest state is kept, so the values yielded in between are
+    /// discarded.
+    pub fn take(&self) -> Option<FiberState<Y, R>> {
+        self.slot.take()
+    }
+}
+
+impl<Y, R> Slot<Y, R> {
+    fn new() -> Self {
+        Self { ptr: AtomicPtr::new(ptr::null_mut()), _marker: PhantomData }
+    }
+
+    fn put(&self, state: FiberState<Y, R>) {
+        let old = self.ptr.swap(Box::into_raw(Box::new(state)), Ordering::AcqRel);
+        if !old.is_null() {
+            drop(unsafe { Box::from_raw(old) });
+        }
+    }
+
+    fn take(&self) -> Option<FiberState<Y, R>> {
+        let ptr = self.ptr.swap(ptr::null_mut(), Ordering::AcqRel);
+        if ptr.is_null() { None } else { Some(*unsafe { Box::from_raw(ptr) }) }
+    }
+}
+
+impl<Y, R> Drop for Slot<Y, R> {
+    fn drop(&mut self) {
+        self.take();
+    }
+}
+
+/// Creates a fiber from the generator `gen`, which reports its progress.
+///
+/// Unlike [`fib::new`](crate::fib::new), the generator can yield and return
+/// arbitrary values. The fiber itself yields `()`, so it can be added to a
+/// fiber chain, and the latest value is available through the returned
+/// [`Progress`](crate::fib::Progress) handle.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(generators)]
+///
+/// use drone_core::fib::{self, Chain, FiberState};
+///
+/// let chain = Chain::new();
+/// let (fib, progress) = fib::new_generator(|| {
+///     yield 1;
+///     2
+/// });
+/// chain.add(fib);
+/// unsafe { chain.drain() };
+/// assert_eq!(progress.take(), Some(FiberState::Yielded(1)));
+/// unsafe { chain.drain() };
+/// assert_eq!(progress.take(), Some(FiberState::Complete(2)));
+/// ```
+#[inline]
+pub fn new_generator<G>(gen: G) -> (FiberProgress<G>, Progress<G::Yield, G::Return>)
+where
+    G: Generator,
+{
+    let slot = Arc::new(Slot::new());
+    (FiberProgress { gen, slot: Arc::clone(&slot) }, Progress { slot })
+}
@@ -0,0 +1,364 @@
+use crate::{
+    fib::{
+        chain::{Node, Root, BASE_PRIORITY},
+        Chain, Fiber, FiberHandle, FiberRoot, FiberState,
+    },
+    thr::Pend,
+};
+use core::{
+    any::Any,
+    pin::Pin,
+    ptr,
+    sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering},
+};
+
+/// A fiber chain with opt-in scheduling features.
+///
+/// [`Chain`] is a single word, so every thread can own one. This type wraps a
+/// [`Chain`] with a selectable [`DrainPolicy`], fiber priorities, suspension,
+/// and a yield observer, at the cost of extra words and extra loads on every
+/// drain.
+pub struct SchedChain {
+    chain: Chain,
+    prioritized: AtomicBool,
+    round_robin: AtomicBool,
+    rotation: AtomicUsize,
+    suspended: AtomicUsize,
+    missed: AtomicBool,
+    observer: AtomicPtr<Observer>,
+}
+
+/// The order in which [`SchedChain::drain`] advances fibers.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DrainPolicy {
+    /// Every drain starts from the most recently added fiber. This is the
+    /// default.
+    Sequential,
+    /// Every drain starts one fiber further than the previous one, so a fiber
+    /// near the head of the chain can't constantly delay the others.
+    RoundRobin,
+}
+
+/// A guard returned by [`SchedChain::suspend`].
+///
+/// Draining of the chain is suspended until the guard is dropped.
+#[must_use = "the chain is resumed immediately if the guard is unused"]
+pub struct SuspendGuard<'a, T: Pend> {
+    chain: &'a SchedChain,
+    thr: &'a T,
+}
+
+struct Observed<F>(F);
+
+struct Observer(Box<dyn FnMut(&dyn Any) + Send>);
+
+impl SchedChain {
+    /// Creates an empty fiber chain.
+    pub const fn new() -> Self {
+        Self {
+            chain: Chain::new(),
+            prioritized: AtomicBool::new(false),
+            round_robin: AtomicBool::new(false),
+            rotation: AtomicUsize::new(0),
+            suspended: AtomicUsize::new(0),
+            missed: AtomicBool::new(false),
+            observer: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    /// Returns the current drain policy.
+    pub fn policy(&self) -> DrainPolicy {
+        if self.round_robin.load(Ordering::Relaxed) {
+            DrainPolicy::RoundRobin
+        } else {
+            DrainPolicy::Sequential
+        }
+    }
+
+    /// Sets the drain policy for subsequent [`SchedChain::drain`] calls.
+    pub fn set_policy(&self, policy: DrainPolicy) {
+        self.round_robin.store(policy == DrainPolicy::RoundRobin, Ordering::Relaxed);
+    }
+
+    /// Adds the fiber `fib` first to the chain.
+    ///
+    /// The fiber has the base priority `0`, so under
+    /// [`DrainPolicy::Sequential`] it is advanced after all fibers added with
+    /// [`SchedChain::add_prioritized`] with higher priorities.
+    pub fn add<F: FiberRoot>(&self, fib: F) {
+        self.chain.add(fib);
+    }
+
+    /// Adds the fiber `fib` to the chain, ordered by descending `priority`.
+    ///
+    /// Under [`DrainPolicy::Sequential`], fibers with higher priorities are
+    /// advanced first. Fibers with equal non-base priorities are advanced in
+    /// the order they were added. Fibers with the base priority `0` are
+    /// advanced last, as if they were added with [`SchedChain::add`].
+    ///
+    /// # Safety
+    ///
+    /// This method walks the chain and links the fiber in the middle of it, so
+    /// it must not be called concurrently with [`SchedChain::drain`] or its
+    /// variants, or with another call to itself, e.g. by a preempting thread.
+    /// It can be called concurrently with [`SchedChain::add`].
+    pub unsafe fn add_prioritized<F: FiberRoot>(&self, fib: F, priority: u8) {
+        let node = Box::into_raw(Box::new(Node::new(fib, priority)));
+        if priority != BASE_PRIORITY {
+            self.prioritized.store(true, Ordering::Relaxed);
+        }
+        loop {
+            let head = self.chain.head.load(Ordering::Acquire);
+            let mut prev: *mut Node = ptr::null_mut();
+            let mut curr = head;
+            while !curr.is_null()
+                && ((*curr).priority == BASE_PRIORITY || (*curr).priority >= priority)
+            {
+                prev = curr;
+                curr = (*curr).next;
+            }
+            (*node).next = curr;
+            if !prev.is_null() {
+                (*prev).next = node;
+                break;
+            }
+            if self.chain.head.compare_and_swap(head, node, Ordering::Release) == head {
+                break;
+            }
+        }
+    }
+
+    /// Adds the fiber `fib` first to the chain, reporting its yielded values to
+    /// the [yield observer](SchedChain::set_yield_observer).
+    ///
+    /// Unlike fibers added with [`SchedChain::add`], the fiber can yield values
+    /// of any type. A fiber producing several items per resumption, e.g.
+    /// draining a FIFO, can yield them as a single [`Vec`], so the observer
+    /// receives them as an ordered batch.
+    pub fn add_observed<F>(&self, fib: F)
+    where
+        F: Fiber<Input = (), Return = ()>,
+        F: Send + 'static,
+        F::Yield: Any,
+    {
+        self.chain.push(Node::new(Observed(fib), BASE_PRIORITY));
+    }
+
+    /// Sets the closure `f` to be called for each fiber yield during a drain,
+    /// replacing the previous one.
+    ///
+    /// The closure receives the yielded value of a fiber added with
+    /// [`SchedChain::add_observed`], or `()` for other fibers. It is called
+    /// while the chain is being drained.
+    ///
+    /// # Safety
+    ///
+    /// The previous closure is dropped immediately, so this method must not be
+    /// called while the chain is being drained, e.g. from a fiber, from the
+    /// observer closure itself, or concurrently on another core.
+    pub unsafe fn set_yield_observer<F: FnMut(&dyn Any) + Send + 'static>(&self, f: F) {
+        let observer = Box::into_raw(Box::new(Observer(Box::new(f))));
+        self.replace_observer(observer);
+    }
+
+    /// Removes the closure set by [`SchedChain::set_yield_observer`].
+    ///
+    /// # Safety
+    ///
+    /// The closure is dropped immediately, so this method must not be called
+    /// while the chain is being drained, e.g. from a fiber, from the observer
+    /// closure itself, or concurrently on another core.
+    pub unsafe fn remove_yield_observer(&self) {
+        self.replace_observer(ptr::null_mut());
+    }
+
+    unsafe fn replace_observer(&self, observer: *mut Observer) {
+        let old = self.observer.swap(observer, Ordering::AcqRel);
+        if !old.is_null() {
+            drop(Box::from_raw(old));
+        }
+    }
+
+    /// Adds the fiber `fib` first to the chain, returning a handle to cancel
+    /// the fiber.
+    pub fn add_with_handle<F: FiberRoot>(&self, fib: F) -> FiberHandle {
+        self.chain.add_with_handle(fib)
+    }
+
+    /// Suspends draining of the chain until the returned guard is dropped.
+    ///
+    /// While the guard is alive, [`SchedChain::drain`] and its variants don't
+    /// advance any fibers. If the chain was drained during the suspension,
+    /// dropping the last guard pends the thread `thr` once, so the missed
+    /// drains are coalesced into a single drain on that thread.
+    pub fn suspend<'a, T: Pend>(&'a self, thr: &'a T) -> SuspendGuard<'a, T> {
+        self.suspended.fetch_add(1, Ordering::AcqRel);
+        SuspendGuard { chain: self, thr }
+    }
+
+    /// Returns `true` if the chain contains no fibers.
+    pub fn is_empty(&self) -> bool {
+        self.chain.is_empty()
+    }
+
+    /// Returns the number of fibers in the chain.
+    ///
+    /// # Safety
+    ///
+    /// See [`Chain::len`].
+    pub unsafe fn len(&self) -> usize {
+        self.chain.len()
+    }
+
+    /// Advances fibers, removing completed ones.
+    ///
+    /// The order of fibers depends on the [`policy`](SchedChain::policy).
+    ///
+    /// # Safety
+    ///
+    /// This method is not reentrant.
+    #[inline(never)]
+    pub unsafe fn drain(&self) {
+        if self.skip_suspended() {
+            return;
+        }
+        self.drain_unguarded();
+    }
+
+    /// Advances fibers starting from the next position in rotation, removing
+    /// completed ones.
+    ///
+    /// This is what [`SchedChain::drain`] does under
+    /// [`DrainPolicy::RoundRobin`].
+    ///
+    /// # Safety
+    ///
+    /// This method is not reentrant.
+    #[inline(never)]
+    pub unsafe fn drain_round_robin(&self) {
+        if self.skip_suspended() {
+            return;
+        }
+        self.drain_rotated();
+    }
+
+    /// Advances every fiber exactly once, removing completed ones. Returns the
+    /// number of remaining fibers.
+    ///
+    /// # Safety
+    ///
+    /// See [`Chain::drain_once`].
+    pub unsafe fn drain_once(&self) -> usize {
+        if !self.skip_suspended() {
+            self.drain_unguarded();
+        }
+        self.len()
+    }
+
+    fn skip_suspended(&self) -> bool {
+        if self.suspended.load(Ordering::Acquire) == 0 {
+            return false;
+        }
+        self.missed.store(true, Ordering::Release);
+        true
+    }
+
+    unsafe fn drain_unguarded(&self) {
+        match self.policy() {
+            DrainPolicy::Sequential => self.drain_sequential(),
+            DrainPolicy::RoundRobin => self.drain_rotated(),
+        }
+    }
+
+    unsafe fn drain_sequential(&self) {
+        if !self.prioritized.load(Ordering::Relaxed) {
+            self.advance_until(
+                ptr::null_mut(),
+                self.chain.head.load(Ordering::Acquire),
+                ptr::null_mut(),
+                |_| true,
+            );
+            return;
+        }
+        for &base in &[false, true] {
+            self.advance_until(
+                ptr::null_mut(),
+                self.chain.head.load(Ordering::Acquire),
+                ptr::null_mut(),
+                |priority| (priority == BASE_PRIORITY) == base,
+            );
+        }
+    }
+
+    unsafe fn drain_rotated(&self) {
+        let len = self.len();
+        if len == 0 {
+            return;
+        }
+        let start = self.rotation.fetch_add(1, Ordering::Relaxed) % len;
+        let mut prev = ptr::null_mut();
+        let mut pivot = self.chain.head.load(Ordering::Acquire);
+        for _ in 0..start {
+            prev = pivot;
+            pivot = (*pivot).next;
+        }
+        let stop = self.advance_until(prev, pivot, ptr::null_mut(), |_| true);
+        if !prev.is_null() {
+            self.advance_until(
+                ptr::null_mut(),
+                self.chain.head.load(Ordering::Acquire),
+                stop,
+                |_| true,
+            );
+        }
+    }
+
+    unsafe fn advance_until(
+        &self,
+        prev: *mut Node,
+        curr: *mut Node,
+        stop: *mut Node,
+        filter: impl Fn(u8) -> bool,
+    ) -> *mut Node {
+        let observer = self.observer.load(Ordering::Acquire).as_mut();
+        self.chain.advance_until(
+            prev,
+            curr,
+            stop,
+            filter,
+            observer.map(|observer| &mut *observer.0 as _),
+        )
+    }
+}
+
+impl<T: Pend> Drop for SuspendGuard<'_, T> {
+    fn drop(&mut self) {
+        if self.chain.suspended.fetch_sub(1, Ordering::AcqRel) == 1
+            && self.chain.missed.swap(false, Ordering::AcqRel)
+        {
+            self.thr.pend();
+        }
+    }
+}
+
+impl<F> Root for Observed<F>
+where
+    F: Fiber<Input = (), Return = ()>,
+    F: Send + 'static,
+    F::Yield: Any,
+{
+    fn advance_root(self: Pin<&mut Self>) -> bool {
+        self.advance_observed(&mut |_| {})
+    }
+
+    fn advance_observed(self: Pin<&mut Self>, observer: &mut dyn FnMut(&dyn Any)) -> bool {
+        let fib = unsafe { self.map_unchecked_mut(|observed| &mut observed.0) };
+        match fib.resume(()) {
+            FiberState::Yielded(value) => {
+                observer(&value);
+                true
+            }
+            FiberState::Complete(()) => false,
+        }
+    }
+}
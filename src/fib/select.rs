@@ -0,0 +1,78 @@
+use crate::fib::{Fiber, FiberState};
+use core::pin::Pin;
+
+/// Fiber racing two fibers.
+///
+/// Can be created with [`fib::select`](crate::fib::select).
+pub struct FiberSelect<A, B>(Option<(A, B)>)
+where
+    A: Fiber<Input = (), Yield = ()>,
+    B: Fiber<Input = (), Yield = ()>;
+
+/// The return value of [`FiberSelect`], indicating which fiber completed
+/// first.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Selected<A, B> {
+    /// The first fiber completed with the value.
+    A(A),
+    /// The second fiber completed with the value.
+    B(B),
+}
+
+impl<A, B> Fiber for FiberSelect<A, B>
+where
+    A: Fiber<Input = (), Yield = ()>,
+    B: Fiber<Input = (), Yield = ()>,
+{
+    type Input = ();
+    type Return = Selected<A::Return, B::Return>;
+    type Yield = ();
+
+    fn resume(self: Pin<&mut Self>, (): ()) -> FiberState<(), Self::Return> {
+        let option = unsafe { &mut self.get_unchecked_mut().0 };
+        match option {
+            Some((a, b)) => {
+                let a = unsafe { Pin::new_unchecked(a) }.resume(());
+                let b = unsafe { Pin::new_unchecked(b) }.resume(());
+                let selected = match (a, b) {
+                    (FiberState::Complete(value), _) => Selected::A(value),
+                    (FiberState::Yielded(()), FiberState::Complete(value)) => Selected::B(value),
+                    (FiberState::Yielded(()), FiberState::Yielded(())) => {
+                        return FiberState::Yielded(());
+                    }
+                };
+                *option = None;
+                FiberState::Complete(selected)
+            }
+            None => panic!("fiber resumed after completion"),
+        }
+    }
+}
+
+/// Creates a fiber, which resumes both fibers `a` and `b` on each resume, and
+/// completes as soon as either of them completes.
+///
+/// The fiber returns [`Selected`] with the return value of the completed
+/// fiber, and drops the other one. If both fibers complete on the same resume,
+/// `a` wins. The fiber is [`Send`] if both `a` and `b` are.
+///
+/// # Examples
+///
+/// ```
+/// use core::pin::Pin;
+/// use drone_core::fib::{self, Fiber, FiberState, Selected};
+///
+/// let mut fib = fib::select(
+///     fib::new_fn(|| FiberState::<(), ()>::Yielded(())),
+///     fib::new_fn(|| FiberState::<(), _>::Complete(1)),
+/// );
+/// assert_eq!(Pin::new(&mut fib).resume(()), FiberState::Complete(Selected::B(1)));
+/// ```
+#[inline]
+pub fn select<A, B>(a: A, b: B) -> FiberSelect<A, B>
+where
+    A: Fiber<Input = (), Yield = ()>,
+    B: Fiber<Input = (), Yield = ()>,
+{
+    FiberSelect(Some((a, b)))
+}
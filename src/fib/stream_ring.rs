@@ -1,11 +1,13 @@
 use crate::{
     fib::{self, Fiber},
-    sync::spsc::ring::{channel, Receiver, SendError, SendErrorKind},
+    sync::spsc::ring::{channel, Receiver, SendError, SendErrorKind, Sender},
     thr::prelude::*,
 };
+use alloc::sync::Arc;
 use core::{
     convert::identity,
     pin::Pin,
+    sync::atomic::{AtomicUsize, Ordering},
     task::{Context, Poll},
 };
 use futures::Stream;
@@ -17,6 +19,7 @@ use futures::Stream;
 #[must_use = "streams do nothing unless you `.await` or poll them"]
 pub struct FiberStreamRing<T> {
     rx: Receiver<T, !>,
+    overruns: Arc<AtomicUsize>,
 }
 
 /// A stream of `Result<T, E>` from the fiber in another thread.
@@ -26,6 +29,7 @@ pub struct FiberStreamRing<T> {
 #[must_use = "streams do nothing unless you `.await` or poll them"]
 pub struct TryFiberStreamRing<T, E> {
     rx: Receiver<T, E>,
+    overruns: Arc<AtomicUsize>,
 }
 
 impl<T> FiberStreamRing<T> {
@@ -36,6 +40,15 @@ impl<T> FiberStreamRing<T> {
     pub fn close(&mut self) {
         self.rx.close()
     }
+
+    /// Returns the number of items lost due to the ring buffer overflow.
+    ///
+    /// Depending on the overflow strategy, these are either new items that
+    /// were skipped, or old items that were overwritten.
+    #[inline]
+    pub fn overruns(&self) -> usize {
+        self.overruns.load(Ordering::Relaxed)
+    }
 }
 
 impl<T, E> TryFiberStreamRing<T, E> {
@@ -46,6 +59,15 @@ impl<T, E> TryFiberStreamRing<T, E> {
     pub fn close(&mut self) {
         self.rx.close()
     }
+
+    /// Returns the number of times the ring buffer overflowed.
+    ///
+    /// Depending on the overflow strategy, each overflow either passed the new
+    /// item to the `overflow` callback, or overwrote an old item.
+    #[inline]
+    pub fn overruns(&self) -> usize {
+        self.overruns.load(Ordering::Relaxed)
+    }
 }
 
 impl<T> Stream for FiberStreamRing<T> {
@@ -86,7 +108,8 @@ pub trait ThrFiberStreamRing: ThrToken {
         F: Send + 'static,
         T: Send + 'static,
     {
-        FiberStreamRing { rx: add_rx(self, capacity, |_| Ok(()), fib, Ok) }
+        let (rx, overruns) = add_rx(self, capacity, |_| Ok(()), fib, Ok);
+        FiberStreamRing { rx, overruns }
     }
 
     /// Adds the fiber `fib` to the fiber chain and returns a stream of `T`
@@ -101,7 +124,8 @@ pub trait ThrFiberStreamRing: ThrToken {
         F: Send + 'static,
         T: Send + 'static,
     {
-        FiberStreamRing { rx: add_rx_overwrite(self, capacity, fib, Ok) }
+        let (rx, overruns) = add_rx_overwrite(self, capacity, fib, Ok);
+        FiberStreamRing { rx, overruns }
     }
 
     /// Adds the fiber `fib` to the fiber chain and returns a stream of
@@ -123,7 +147,8 @@ pub trait ThrFiberStreamRing: ThrToken {
         T: Send + 'static,
         E: Send + 'static,
     {
-        TryFiberStreamRing { rx: add_rx(self, capacity, overflow, fib, identity) }
+        let (rx, overruns) = add_rx(self, capacity, overflow, fib, identity);
+        TryFiberStreamRing { rx, overruns }
     }
 
     /// Adds the fiber `fib` to the fiber chain and returns a stream of
@@ -143,7 +168,8 @@ pub trait ThrFiberStreamRing: ThrToken {
         T: Send + 'static,
         E: Send + 'static,
     {
-        TryFiberStreamRing { rx: add_rx_overwrite(self, capacity, fib, identity) }
+        let (rx, overruns) = add_rx_overwrite(self, capacity, fib, identity);
+        TryFiberStreamRing { rx, overruns }
     }
 }
 
@@ -154,7 +180,7 @@ fn add_rx<H, O, F, T, E, C>(
     overflow: O,
     mut fib: F,
     convert: C,
-) -> Receiver<T, E>
+) -> (Receiver<T, E>, Arc<AtomicUsize>)
 where
     H: ThrToken,
     O: Fn(T) -> Result<(), E>,
@@ -167,6 +193,12 @@ where
     C: Send + 'static,
 {
     let (mut tx, rx) = channel(capacity);
+    let overruns = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&overruns);
+    let count_overflow = move |value| {
+        counter.fetch_add(1, Ordering::Relaxed);
+        overflow(value)
+    };
     thr.add(move || {
         loop {
             if tx.is_canceled() {
@@ -180,7 +212,7 @@ where
                         SendErrorKind::Canceled => {
                             break;
                         }
-                        SendErrorKind::Overflow => match overflow(value) {
+                        SendErrorKind::Overflow => match count_overflow(value) {
                             Ok(()) => {}
                             Err(err) => {
                                 drop(tx.send_err(err));
@@ -196,7 +228,7 @@ where
                             Ok(()) => {}
                             Err(SendError { value, kind }) => match kind {
                                 SendErrorKind::Canceled => {}
-                                SendErrorKind::Overflow => match overflow(value) {
+                                SendErrorKind::Overflow => match count_overflow(value) {
                                     Ok(()) => {}
                                     Err(err) => {
                                         drop(tx.send_err(err));
@@ -214,7 +246,7 @@ where
             yield;
         }
     });
-    rx
+    (rx, overruns)
 }
 
 #[inline]
@@ -223,7 +255,7 @@ fn add_rx_overwrite<H, F, T, E, C>(
     capacity: usize,
     mut fib: F,
     convert: C,
-) -> Receiver<T, E>
+) -> (Receiver<T, E>, Arc<AtomicUsize>)
 where
     H: ThrToken,
     F: Fiber<Input = (), Yield = Option<T>>,
@@ -234,6 +266,16 @@ where
     C: Send + 'static,
 {
     let (mut tx, rx) = channel(capacity);
+    let overruns = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&overruns);
+    let send_overwrite = move |tx: &mut Sender<T, E>, value| match tx.send(value) {
+        Ok(()) => Ok(()),
+        Err(SendError { value, kind: SendErrorKind::Canceled }) => Err(value),
+        Err(SendError { value, kind: SendErrorKind::Overflow }) => {
+            counter.fetch_add(1, Ordering::Relaxed);
+            tx.send_overwrite(value)
+        }
+    };
     thr.add(move || {
         loop {
             if tx.is_canceled() {
@@ -241,7 +283,7 @@ where
             }
             match unsafe { Pin::new_unchecked(&mut fib) }.resume(()) {
                 fib::Yielded(None) => {}
-                fib::Yielded(Some(value)) => match tx.send_overwrite(value) {
+                fib::Yielded(Some(value)) => match send_overwrite(&mut tx, value) {
                     Ok(()) => (),
                     Err(_) => break,
                 },
@@ -249,7 +291,7 @@ where
                     match convert(value) {
                         Ok(None) => {}
                         Ok(Some(value)) => {
-                            drop(tx.send_overwrite(value));
+                            drop(send_overwrite(&mut tx, value));
                         }
                         Err(err) => {
                             drop(tx.send_err(err));
@@ -261,7 +303,7 @@ where
             yield;
         }
     });
-    rx
+    (rx, overruns)
 }
 
 impl<T: ThrToken> ThrFiberStreamRing for T {}
@@ -0,0 +1,69 @@
+use crate::fib::{Fiber, FiberRoot, FiberState};
+use core::pin::Pin;
+
+/// Fiber continuing with another fiber built from the return value of the
+/// first one.
+///
+/// Can be created with [`FiberExt::then`](crate::fib::FiberExt::then).
+pub struct FiberThen<Fb, F, G> {
+    first: Option<Fb>,
+    next: Option<F>,
+    second: Option<G>,
+}
+
+impl<Fb, F, G> FiberThen<Fb, F, G> {
+    pub(crate) fn new(fib: Fb, next: F) -> Self {
+        Self { first: Some(fib), next: Some(next), second: None }
+    }
+}
+
+impl<Fb, F, G> Fiber for FiberThen<Fb, F, G>
+where
+    Fb: Fiber<Input = ()>,
+    F: FnOnce(Fb::Return) -> G,
+    G: Fiber<Input = (), Yield = Fb::Yield>,
+{
+    type Input = ();
+    type Return = G::Return;
+    type Yield = Fb::Yield;
+
+    fn resume(self: Pin<&mut Self>, (): ()) -> FiberState<Fb::Yield, G::Return> {
+        let Self { first, next, second } = unsafe { self.get_unchecked_mut() };
+        if let Some(fib) = first {
+            match unsafe { Pin::new_unchecked(fib) }.resume(()) {
+                FiberState::Yielded(value) => return FiberState::Yielded(value),
+                FiberState::Complete(value) => {
+                    *first = None;
+                    let next = next.take().expect("fiber resumed after completion");
+                    *second = Some(next(value));
+                }
+            }
+        }
+        let fib = second.as_mut().expect("fiber resumed after completion");
+        match unsafe { Pin::new_unchecked(fib) }.resume(()) {
+            FiberState::Yielded(value) => FiberState::Yielded(value),
+            FiberState::Complete(value) => {
+                *second = None;
+                FiberState::Complete(value)
+            }
+        }
+    }
+}
+
+impl<Fb, F, G> FiberRoot for FiberThen<Fb, F, G>
+where
+    Fb: Fiber<Input = (), Yield = ()>,
+    Fb: Send + 'static,
+    F: FnOnce(Fb::Return) -> G,
+    F: Send + 'static,
+    G: Fiber<Input = (), Yield = (), Return = ()>,
+    G: Send + 'static,
+{
+    #[inline]
+    fn advance(self: Pin<&mut Self>) -> bool {
+        match self.resume(()) {
+            FiberState::Yielded(()) => true,
+            FiberState::Complete(()) => false,
+        }
+    }
+}
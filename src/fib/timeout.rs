@@ -0,0 +1,58 @@
+use crate::fib::{Fiber, FiberState};
+use core::{fmt, pin::Pin};
+
+/// Fiber completing early if another fiber takes too many polls.
+///
+/// Can be created with
+/// [`FiberExt::timeout_polls`](crate::fib::FiberExt::timeout_polls).
+pub struct FiberTimeout<Fb> {
+    fib: Option<Fb>,
+    remaining: usize,
+}
+
+/// An error indicating that a fiber hasn't completed within the given number
+/// of polls.
+///
+/// This error is returned by
+/// [`FiberExt::timeout_polls`](crate::fib::FiberExt::timeout_polls) fibers.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TimedOut;
+
+impl<Fb> FiberTimeout<Fb> {
+    pub(crate) fn new(fib: Fb, polls: usize) -> Self {
+        Self { fib: Some(fib), remaining: polls }
+    }
+}
+
+impl<Fb: Fiber> Fiber for FiberTimeout<Fb> {
+    type Input = Fb::Input;
+    type Return = Result<Fb::Return, TimedOut>;
+    type Yield = Fb::Yield;
+
+    fn resume(self: Pin<&mut Self>, input: Fb::Input) -> FiberState<Fb::Yield, Self::Return> {
+        let Self { fib, remaining } = unsafe { self.get_unchecked_mut() };
+        if *remaining == 0 {
+            *fib = None;
+            return FiberState::Complete(Err(TimedOut));
+        }
+        *remaining -= 1;
+        let inner = fib.as_mut().expect("fiber resumed after completion");
+        match unsafe { Pin::new_unchecked(inner) }.resume(input) {
+            FiberState::Yielded(value) if *remaining > 0 => FiberState::Yielded(value),
+            FiberState::Yielded(_) => {
+                *fib = None;
+                FiberState::Complete(Err(TimedOut))
+            }
+            FiberState::Complete(value) => {
+                *fib = None;
+                FiberState::Complete(Ok(value))
+            }
+        }
+    }
+}
+
+impl fmt::Display for TimedOut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "fiber timed out")
+    }
+}
@@ -0,0 +1,89 @@
+use crate::{
+    bitfield::Bitfield,
+    reg::{tag::RegTag, RReg, Reg, WReg},
+};
+use core::{
+    marker::PhantomData,
+    ptr::{read_volatile, write_volatile},
+};
+
+/// A register token bound to an address known only at run-time.
+///
+/// Regular register tokens are zero-sized and use the address declared in
+/// [`reg!`](crate::reg!). This wrapper stores the address instead, which is
+/// useful when peripheral base addresses are discovered at boot. The wrapped
+/// token is still used to construct and inspect register values.
+pub struct DynReg<T: RegTag, R: Reg<T>> {
+    reg: R,
+    address: usize,
+    _tag: PhantomData<T>,
+}
+
+impl<T: RegTag, R: Reg<T>> DynReg<T, R> {
+    /// Binds the register token `reg` to the memory at `base`.
+    ///
+    /// # Safety
+    ///
+    /// `base` must be a valid and properly aligned address of a register with
+    /// the layout of `R`. While the returned token is alive, the memory at
+    /// `base` must not be accessed through any other token or pointer, unless
+    /// the access is synchronized with this token.
+    #[inline]
+    pub unsafe fn from_base(reg: R, base: usize) -> Self {
+        Self { reg, address: base, _tag: PhantomData }
+    }
+
+    /// Returns the address of the register memory.
+    #[inline]
+    pub fn address(&self) -> usize {
+        self.address
+    }
+
+    /// Returns a reference to the wrapped register token.
+    #[inline]
+    pub fn reg(&self) -> &R {
+        &self.reg
+    }
+
+    /// Releases the wrapped register token.
+    #[inline]
+    pub fn into_reg(self) -> R {
+        self.reg
+    }
+
+    /// Reads the value from the register memory to the opaque value type.
+    #[inline]
+    pub fn load_val(&self) -> R::Val
+    where
+        R: RReg<T>,
+    {
+        unsafe {
+            R::val_from(R::bits_from_mem(read_volatile(
+                self.address as *const <R::Val as Bitfield>::Bits,
+            )))
+        }
+    }
+
+    /// Writes an opaque value `val` into the register memory.
+    #[inline]
+    pub fn store_val(&mut self, val: R::Val)
+    where
+        R: WReg<T>,
+    {
+        unsafe {
+            write_volatile(
+                self.address as *mut <R::Val as Bitfield>::Bits,
+                R::bits_to_mem(val.bits()),
+            );
+        }
+    }
+
+    /// Writes the reset value into the register memory.
+    #[inline]
+    pub fn reset(&mut self)
+    where
+        R: WReg<T>,
+    {
+        self.store_val(self.reg.default_val());
+    }
+}
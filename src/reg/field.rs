@@ -75,6 +75,27 @@ pub trait RegFieldBit<T: RegTag>: RegField<T> {}
 /// Multiple-bits register field.
 pub trait RegFieldBits<T: RegTag>: RegField<T> {}
 
+/// A type stored in a multiple-bit register field.
+///
+/// A field can be mapped to the type with `as` suffix in [`reg!`] macro:
+/// `FOO { 1 3 RRRegField WWRegField } as Mode`. This generates `read_foo()`
+/// and `write_foo(value)` methods, which convert the field bits with this
+/// trait.
+///
+/// [`reg!`]: crate::reg!
+pub trait RegFieldEnum<B: Bits>: Sized {
+    /// Converts the field `bits` into a value. Returns `None` if `bits` don't
+    /// correspond to any value.
+    fn from_bits(bits: B) -> Option<Self>;
+
+    /// Returns a value for `bits` rejected by
+    /// [`from_bits`](RegFieldEnum::from_bits).
+    fn fallback(bits: B) -> Self;
+
+    /// Converts the value into the field bits.
+    fn into_bits(self) -> B;
+}
+
 /// Readable field of readable register.
 pub trait RRRegField<T: RegTag>
 where
@@ -85,9 +106,9 @@ where
     #[inline]
     fn load_val(&self) -> <Self::Reg as Reg<T>>::Val {
         unsafe {
-            Self::Reg::val_from(read_volatile(
+            Self::Reg::val_from(Self::Reg::bits_from_mem(read_volatile(
                 Self::Reg::ADDRESS as *const <<Self::Reg as Reg<T>>::Val as Bitfield>::Bits,
-            ))
+            )))
         }
     }
 }
@@ -100,6 +121,45 @@ where
 {
 }
 
+/// Write-one-to-clear field of writable register.
+///
+/// Writing ones to the field clears it, and writing zeros has no effect. Such
+/// fields must not be written back after a read, so they don't provide
+/// read-modify-write methods. Can't be combined with [`WWRegField`].
+pub trait W1cRegField<T: RegTag>
+where
+    Self: RegField<T>,
+    Self::Reg: WReg<T>,
+{
+    /// Creates a new opaque register value with the field bits set and all
+    /// other bits cleared.
+    #[inline]
+    fn clear_val(&self) -> <Self::Reg as Reg<T>>::Val {
+        unsafe {
+            let mut val =
+                Self::Reg::val_from(<<Self::Reg as Reg<T>>::Val as Bitfield>::Bits::from_usize(0));
+            val.write_bits(
+                <<Self::Reg as Reg<T>>::Val as Bitfield>::Bits::from_usize(Self::OFFSET),
+                <<Self::Reg as Reg<T>>::Val as Bitfield>::Bits::from_usize(Self::WIDTH),
+                !<<Self::Reg as Reg<T>>::Val as Bitfield>::Bits::from_usize(0),
+            );
+            val
+        }
+    }
+
+    /// Writes [`clear_val`](W1cRegField::clear_val) into the register memory,
+    /// without reading it first.
+    #[inline]
+    fn store_clear(&self) {
+        unsafe {
+            write_volatile(
+                Self::Reg::ADDRESS as *mut <<Self::Reg as Reg<T>>::Val as Bitfield>::Bits,
+                Self::Reg::bits_to_mem(self.clear_val().bits()),
+            );
+        }
+    }
+}
+
 /// Read-only field of readable register.
 pub trait RoRRegField<T: RegTag>
 where
@@ -243,7 +303,7 @@ where
         unsafe {
             write_volatile(
                 Self::Reg::ADDRESS as *mut <<Self::Reg as Reg<T>>::Val as Bitfield>::Bits,
-                val.bits(),
+                Self::Reg::bits_to_mem(val.bits()),
             );
         }
     }
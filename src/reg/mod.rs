@@ -94,6 +94,8 @@
 //! | [`read_bits`](reg::field::RRRegFieldBits::read_bits)     | multi-bit | read  | read       |
 //! | [`write`](reg::field::WWRegFieldBits::write)             | multi-bit | write | write      |
 //! | [`write_bits`](reg::field::WoWoRegFieldBits::write_bits) | multi-bit | write | write-only |
+//! | [`clear_val`](reg::field::W1cRegField::clear_val)        |           | w1c   | write      |
+//! | [`store_clear`](reg::field::W1cRegField::store_clear)    |           | w1c   | write      |
 //!
 //! ## Register Token
 //!
@@ -121,6 +123,16 @@
 //! | [`reset`](reg::WRegAtomic::reset)           | write      | Srt, Crt |
 //! | [`modify`](reg::RwRegUnsync::modify)        | read-write | Urt      |
 //!
+//! Atomic read-modify-write operations for [`Srt`](reg::tag::Srt) and
+//! [`Crt`](reg::tag::Crt) tokens depend on the target architecture (e.g.
+//! exclusive load/store instructions or masking interrupts), and therefore are
+//! provided by platform crates.
+//!
+//! `reg::read_group!(a, b, ...)` loads several readable registers in one call,
+//! returning a tuple of their values. The registers are read in the order of
+//! the arguments, which matters when reading one register has side-effects on
+//! another, e.g. reading a status register clears flags.
+//!
 //! ## Register Value
 //!
 //! Autogenerated field methods for [`RegHold`](reg::RegHold) (`foo` as an
@@ -135,6 +147,79 @@
 //! | `toggle_foo()` ([`toggle`](reg::field::WWRegFieldBit::toggle))   | one-bit   | write |
 //! | `write_foo(bits)` ([`write`](reg::field::WWRegFieldBits::write)) | multi-bit | write |
 //!
+//! For a single-bit field, which is both readable and writable, the
+//! [`Urt`](reg::tag::Urt) register token also has `toggle_foo()` method, which
+//! toggles the bit in the register memory with
+//! [`modify`](reg::RwRegUnsync::modify). Tokens with atomic tags have
+//! `toggle_foo()` method too, which toggles the bit atomically (see
+//! [`toggle_bit`](reg::RwRegAtomicBit::toggle_bit)), and `test_and_set_foo()`
+//! method, which atomically sets the bit in the register memory and returns
+//! its previous value (see
+//! [`test_and_set_bit`](reg::RwRegAtomicBit::test_and_set_bit)). The atomic
+//! operations themselves are provided by the platform crate (see
+//! [`AtomicBits`](reg::AtomicBits)). They are available for registers up to
+//! 32 bits wide.
+//!
+//! A register declared without `RReg`, e.g. a command register which faults on
+//! reads, has no read methods at all: no `load`, no `modify`, and no field
+//! getters. Declaring a `RRRegField` field in such register is an error.
+//!
+//! Fields may overlap as long as no bit is covered by two readable or two
+//! writable fields. This allows aliasing a bit, which means "busy" when read
+//! and "start" when written, under two names: `BUSY { 0 1 RRRegField
+//! RoRRegField }` and `START { 0 1 WWRegField WoWRegField }`.
+//!
+//! A multi-bit field can be mapped to a type implementing
+//! [`RegFieldEnum`](reg::field::RegFieldEnum) with `as` suffix, e.g. `FOO { 1 3
+//! RRRegField WWRegField } as Mode`. In that case `read_foo()` returns `Mode`,
+//! and `write_foo(value)` takes `Mode` instead of raw bits.
+//!
+//! For each field `FOO` the register module also contains `FOO_MASK` and
+//! `FOO_OFFSET` constants, which can be used for bit math at const-eval time.
+//! Doc comments on a field declaration are attached to the field token, the
+//! generated field methods, and these constants.
+//! The register address and reset value are available as
+//! [`Reg::ADDRESS`](reg::Reg::ADDRESS) and [`Reg::RESET`](reg::Reg::RESET)
+//! associated constants of the register token, e.g. for generating a
+//! peripheral map at build time.
+//!
+//! `Val::fields()` extracts all field values at once into a generated `Fields`
+//! struct with a member per field, e.g. for pattern matching. Single-bit
+//! fields are represented as `bool`, and multi-bit fields as raw bits, even if
+//! mapped to a type.
+//!
+//! The opaque register value type `Val` implements `|`, `&`, `^`, and `!`
+//! operators over the whole register width. It converts into the backing
+//! integer with `From`. The way back goes through the register token:
+//! `from_bits_checked` and `try_val_from_bits` reject reserved bits, and if the
+//! fields cover all bits of the register, the infallible `val_from_bits` is
+//! generated as well. The `Debug` implementation prints each field with its
+//! value, followed by the reserved bits if any of them are set.
+//!
+//! `Val::bits` and the unsafe `Val::from_raw` convert the value to the backing
+//! integer and back without any checks, preserving reserved bits. They can be
+//! used to hand a register snapshot over from an interrupt handler to a thread,
+//! without re-reading the register. The raw bits can be stashed in an atomic
+//! integer, e.g. a field of a thread object marked with `#[atomic]`. The
+//! snapshot is just a copy of the bits, so the reader won't observe later
+//! changes of the register.
+//!
+//! When compiled for an ARMv7-M target, a single-bit writable field `foo` of a
+//! register located within the first megabyte of the SRAM (`0x2000_0000`) or
+//! the peripheral (`0x4000_0000`) region also gets `set_foo_bitband()` and
+//! `clear_foo_bitband()` methods on the register token. They write to the
+//! [bit-band alias](reg::bitband_alias) of the bit, which is atomic and faster
+//! than a read-modify-write. The methods are not generated for big-endian
+//! registers, because the alias addresses physical bits. Use them only on
+//! cores supporting bit-banding, e.g. Cortex-M3 and Cortex-M4, but not
+//! Cortex-M7.
+//!
+//! A register attached to a bus with a different byte order can be declared
+//! with `#[endian = "big"]` or `#[endian = "little"]` attribute. The register
+//! value is then byte-swapped as needed on every load and store, so field
+//! offsets stay logical rather than physical. Registers are native-endian by
+//! default.
+//!
 //! # Tags
 //!
 //! Each register or field token can have one of three flavors. They are encoded
@@ -200,12 +285,14 @@
 //!     /// SysTick control and status register.
 //!     pub mod STK CTRL;
 //!     0xE000_E010 // the register address in memory
-//!     0x20        // size of the register in bits
+//!     0x20        // size of the register in bits: 8, 16, 32, or 64
 //!     0x0000_0000 // reset value of the register
 //!     // Traits to implement for the register token. The most common sets are:
 //!     //     RReg RoReg - read-only register
 //!     //     RReg WReg  - read-write register
 //!     //     WReg WoReg - write-only register
+//!     // Adding `AtomicReg` restricts memory access to `Srt` and `Crt`
+//!     // tokens.
 //!     RReg WReg;
 //!
 //!     // Here we define register fields.
@@ -217,6 +304,7 @@
 //!         //     RRRegField RoRRegField - read-only field
 //!         //     RRRegField WWRegField  - read-write field
 //!         //     WWRegField WoWRegField - read-write field
+//!         //     RRRegField W1cRegField - write-one-to-clear field
 //!         RRRegField WWRegField
 //!     }
 //! }
@@ -285,21 +373,65 @@
 //!
 //! fn trunk(reg: Regs) {}
 //! ```
+//!
+//! The index also implements [`CheckedToken`](crate::token::CheckedToken).
+//! `Regs::take_all()` is a safe alternative to `Regs::take()`, which returns an
+//! error if the index has already been taken. It doesn't account for the
+//! instances created with `Regs::take()`, so only one of them should be used.
+//! The index stays taken until it is released with `CheckedToken::release`;
+//! dropping it is not enough. `Regs::split()` converts the index into a tuple
+//! of the register tokens in declaration order, which also keeps the index
+//! taken.
+//!
+//! The index has no syntax for register arrays, like `TEST_REG[4];`. Every
+//! register token is a distinct zero-sized type with its address fixed at
+//! compile-time, so an indexed accessor would have to return a different type
+//! per index. Peripherals with several identical register sets should declare
+//! each set in the index, or bind one set to run-time addresses with
+//! [`DynReg`].
 
 pub mod field;
 pub mod marker;
 pub mod prelude;
 pub mod tag;
 
+mod dynamic;
+
+pub use self::dynamic::DynReg;
+
 /// A macro to define a macro to define a set of register tokens.
 ///
 /// See [the module level documentation](self) for details.
 #[doc(inline)]
 pub use drone_core_macros::reg_tokens as tokens;
 
+/// A macro to read several registers in a defined order.
+///
+/// `read_group!(a, b, ...)` calls `load_val()` on each register token in the
+/// order of the arguments, and returns a tuple of the values. Volatile reads
+/// are never reordered with each other, so the hardware observes the reads in
+/// the same order.
+#[doc(inline)]
+pub use crate::reg_read_group as read_group;
+
 use self::tag::{Crt, RegAtomic, RegOwned, RegTag, Srt, Urt};
-use crate::{bitfield::Bitfield, token::Token};
-use core::ptr::{read_volatile, write_volatile};
+use crate::{
+    bitfield::{Bitfield, Bits},
+    token::Token,
+};
+use core::{
+    fmt,
+    ptr::{read_volatile, write_volatile},
+};
+
+extern "C" {
+    fn drone_reg_fetch_or_u8(ptr: *mut u8, bits: u8) -> u8;
+    fn drone_reg_fetch_or_u16(ptr: *mut u16, bits: u16) -> u16;
+    fn drone_reg_fetch_or_u32(ptr: *mut u32, bits: u32) -> u32;
+    fn drone_reg_fetch_xor_u8(ptr: *mut u8, bits: u8) -> u8;
+    fn drone_reg_fetch_xor_u16(ptr: *mut u16, bits: u16) -> u16;
+    fn drone_reg_fetch_xor_u32(ptr: *mut u32, bits: u32) -> u32;
+}
 
 /// The base trait for a memory-mapped register token.
 pub trait Reg<T: RegTag>: Token + Sync {
@@ -334,7 +466,31 @@ pub trait Reg<T: RegTag>: Token + Sync {
     /// This function is unsafe because it doesn't require a token.
     unsafe fn val_from(bits: <Self::Val as Bitfield>::Bits) -> Self::Val;
 
+    /// Converts `bits` as stored in the register memory into the logical bit
+    /// order of [`Reg::Val`].
+    ///
+    /// This is a no-op unless the register is declared with a non-native
+    /// `#[endian]` attribute in [`reg!`](crate::reg!).
+    #[inline]
+    fn bits_from_mem(bits: <Self::Val as Bitfield>::Bits) -> <Self::Val as Bitfield>::Bits {
+        bits
+    }
+
+    /// Converts `bits` in the logical bit order of [`Reg::Val`] into the form
+    /// stored in the register memory.
+    ///
+    /// This is the inverse of [`Reg::bits_from_mem`].
+    #[inline]
+    fn bits_to_mem(bits: <Self::Val as Bitfield>::Bits) -> <Self::Val as Bitfield>::Bits {
+        bits
+    }
+
     /// Converts into unsynchronized register token.
+    ///
+    /// The conversion is zero-cost. It is safe because the token is consumed,
+    /// so the unsynchronized token is the only owner of the register, e.g.
+    /// during a single-threaded initialization phase. Use
+    /// [`into_sync`](Reg::into_sync) to convert back.
     #[inline]
     fn into_unsync(self) -> Self::UReg
     where
@@ -377,6 +533,16 @@ pub trait Reg<T: RegTag>: Token + Sync {
     }
 }
 
+/// An error indicating that reserved bits are set in a raw register value.
+///
+/// A bit is reserved if it is not covered by any field declared in [`reg!`].
+/// This error is returned by the `Reg::from_bits_checked` method generated by
+/// [`reg!`].
+///
+/// [`reg!`]: crate::reg!
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ReservedBitsError<B: Bits>(B);
+
 /// Connects [`Reg`] with [`RegHold`].
 pub trait RegRef<'a, T: RegTag>: Reg<T> {
     /// Exposed storage for register values.
@@ -440,7 +606,7 @@ pub trait RReg<T: RegTag>: Reg<T> {
     /// See also [`load`](RReg::load), [`load_val`](RReg::load_val).
     #[inline]
     fn load_bits(&self) -> <Self::Val as Bitfield>::Bits {
-        unsafe { read_volatile(self.as_ptr()) }
+        Self::bits_from_mem(unsafe { read_volatile(self.as_ptr()) })
     }
 
     /// Returns a raw pointer to the register memory.
@@ -469,6 +635,88 @@ pub trait RoReg<T: RegTag>: RReg<T> {}
 /// Write-only register.
 pub trait WoReg<T: RegTag>: WReg<T> {}
 
+/// Register, which can only be accessed with atomic operations.
+///
+/// Tokens of such register exist with all tags, but [`reg!`](crate::reg!)
+/// implements [`RReg`], [`WReg`], and the field traits only for [`Srt`] and
+/// [`Crt`] tags, so a [`Urt`] token can't access the memory. This is useful for
+/// registers shared between independent drivers, where a non-atomic
+/// read-modify-write would lose concurrent updates.
+pub trait AtomicReg<T: RegAtomic>: Reg<T> {}
+
+/// Atomic single-bit operations for read-write register.
+pub trait RwRegAtomicBit<T: RegAtomic>: RReg<T> + WReg<T>
+where
+    <Self::Val as Bitfield>::Bits: AtomicBits,
+{
+    /// Atomically sets the bit at `offset` in the register memory, returning
+    /// the previous value of the bit.
+    ///
+    /// This is a single atomic read-modify-write operation provided by the
+    /// platform crate (see [`AtomicBits`]), e.g. an exclusive load/store loop
+    /// on ARMv7-M, so it can be used to build a simple lock on a hardware bit.
+    #[inline]
+    fn test_and_set_bit(&self, offset: usize) -> bool {
+        let mask = <Self::Val as Bitfield>::Bits::from_usize(1)
+            << <Self::Val as Bitfield>::Bits::from_usize(offset);
+        let prev = unsafe { AtomicBits::fetch_or(self.as_mut_ptr(), Self::bits_to_mem(mask)) };
+        !(Self::bits_from_mem(prev) & mask).is_zero()
+    }
+
+    /// Atomically toggles the bit at `offset` in the register memory.
+    ///
+    /// This is a single atomic read-modify-write operation provided by the
+    /// platform crate (see [`AtomicBits`]).
+    #[inline]
+    fn toggle_bit(&self, offset: usize) {
+        let mask = <Self::Val as Bitfield>::Bits::from_usize(1)
+            << <Self::Val as Bitfield>::Bits::from_usize(offset);
+        unsafe { AtomicBits::fetch_xor(self.as_mut_ptr(), Self::bits_to_mem(mask)) };
+    }
+}
+
+/// Register value integer types supporting atomic read-modify-write
+/// operations.
+///
+/// The operations depend on the target architecture, so they are not
+/// implemented here. Instead they are forwarded to the following functions,
+/// which must be defined by the platform crate. The functions must access the
+/// memory at `ptr` as volatile, since it is a memory-mapped register. The
+/// symbols are required as soon as an atomic bit operation is used, e.g. by a
+/// `test_and_set_foo()` or an atomic `toggle_foo()` method.
+///
+/// ```ignore
+/// #[no_mangle]
+/// extern "C" fn drone_reg_fetch_or_u8(ptr: *mut u8, bits: u8) -> u8;
+/// #[no_mangle]
+/// extern "C" fn drone_reg_fetch_or_u16(ptr: *mut u16, bits: u16) -> u16;
+/// #[no_mangle]
+/// extern "C" fn drone_reg_fetch_or_u32(ptr: *mut u32, bits: u32) -> u32;
+/// #[no_mangle]
+/// extern "C" fn drone_reg_fetch_xor_u8(ptr: *mut u8, bits: u8) -> u8;
+/// #[no_mangle]
+/// extern "C" fn drone_reg_fetch_xor_u16(ptr: *mut u16, bits: u16) -> u16;
+/// #[no_mangle]
+/// extern "C" fn drone_reg_fetch_xor_u32(ptr: *mut u32, bits: u32) -> u32;
+/// ```
+pub trait AtomicBits: Bits {
+    /// Atomically sets `bits` in the memory at `ptr`, returning the previous
+    /// value.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be valid for reads and writes, and properly aligned.
+    unsafe fn fetch_or(ptr: *mut Self, bits: Self) -> Self;
+
+    /// Atomically toggles `bits` in the memory at `ptr`, returning the
+    /// previous value.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be valid for reads and writes, and properly aligned.
+    unsafe fn fetch_xor(ptr: *mut Self, bits: Self) -> Self;
+}
+
 /// Non-atomic operations for writable register.
 // FIXME https://github.com/rust-lang/rust/issues/46397
 pub trait WRegUnsync<'a>: WReg<Urt> + RegRef<'a, Urt> {
@@ -557,7 +805,10 @@ where
         ) -> &'b mut <Self as RegRef<'a, Urt>>::Hold,
     {
         unsafe {
-            write_volatile(self.as_mut_ptr(), f(&mut self.default()).val().bits());
+            write_volatile(
+                self.as_mut_ptr(),
+                Self::bits_to_mem(f(&mut self.default()).val().bits()),
+            );
         }
     }
 
@@ -568,12 +819,12 @@ where
 
     #[inline]
     fn store_bits(&mut self, bits: <Self::Val as Bitfield>::Bits) {
-        unsafe { write_volatile(self.as_mut_ptr(), bits) };
+        unsafe { write_volatile(self.as_mut_ptr(), Self::bits_to_mem(bits)) };
     }
 
     #[inline]
     fn reset(&'a mut self) {
-        unsafe { write_volatile(self.as_mut_ptr(), self.default_val().bits()) };
+        self.store_val(self.default_val());
     }
 }
 
@@ -601,7 +852,7 @@ where
 
     #[inline]
     fn store_bits(&self, bits: <Self::Val as Bitfield>::Bits) {
-        unsafe { write_volatile(self.as_mut_ptr(), bits) };
+        unsafe { write_volatile(self.as_mut_ptr(), Self::bits_to_mem(bits)) };
     }
 
     #[inline]
@@ -610,6 +861,14 @@ where
     }
 }
 
+impl<T, R> RwRegAtomicBit<T> for R
+where
+    T: RegAtomic,
+    R: RReg<T> + WReg<T>,
+    <R::Val as Bitfield>::Bits: AtomicBits,
+{
+}
+
 impl<'a, R> RwRegUnsync<'a> for R
 where
     R: RReg<Urt> + WRegUnsync<'a> + RegRef<'a, Urt>,
@@ -622,11 +881,81 @@ where
         ) -> &'b mut <Self as RegRef<'a, Urt>>::Hold,
     {
         unsafe {
-            write_volatile(self.as_mut_ptr(), f(&mut self.load()).val().bits());
+            write_volatile(self.as_mut_ptr(), Self::bits_to_mem(f(&mut self.load()).val().bits()));
         }
     }
 }
 
+macro_rules! atomic_bits {
+    ($type:ty, $fetch_or:ident, $fetch_xor:ident) => {
+        impl AtomicBits for $type {
+            #[inline]
+            unsafe fn fetch_or(ptr: *mut Self, bits: Self) -> Self {
+                $fetch_or(ptr, bits)
+            }
+
+            #[inline]
+            unsafe fn fetch_xor(ptr: *mut Self, bits: Self) -> Self {
+                $fetch_xor(ptr, bits)
+            }
+        }
+    };
+}
+
+atomic_bits!(u8, drone_reg_fetch_or_u8, drone_reg_fetch_xor_u8);
+atomic_bits!(u16, drone_reg_fetch_or_u16, drone_reg_fetch_xor_u16);
+atomic_bits!(u32, drone_reg_fetch_or_u32, drone_reg_fetch_xor_u32);
+
+/// Returns the bit-band alias address for the bit `bit` of the word at
+/// `address`.
+///
+/// On ARM Cortex-M3 and Cortex-M4 cores each bit of the first megabyte of the
+/// SRAM and peripheral regions is mapped to a word in the corresponding alias
+/// region. A single write to the alias word sets or clears the bit, which is
+/// inherently atomic. `address` must be within one of these bit-band regions.
+///
+/// # Examples
+///
+/// ```
+/// use drone_core::reg::bitband_alias;
+///
+/// assert_eq!(bitband_alias(0x4001_080C, 3), 0x4221_018C);
+/// ```
+#[inline]
+pub const fn bitband_alias(address: usize, bit: u32) -> usize {
+    (address & 0xF000_0000) + 0x0200_0000 + ((address & 0x000F_FFFF) << 5) + ((bit as usize) << 2)
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! reg_read_group {
+    ($($reg:expr),+ $(,)?) => {{
+        #[allow(unused_imports)]
+        use $crate::reg::RReg as _;
+        ($(($reg).load_val(),)+)
+    }};
+}
+
+impl<B: Bits> ReservedBitsError<B> {
+    /// Creates a new error for the set reserved bits `reserved`.
+    #[inline]
+    pub fn new(reserved: B) -> Self {
+        Self(reserved)
+    }
+
+    /// Returns the reserved bits that were set.
+    #[inline]
+    pub fn reserved_bits(&self) -> B {
+        self.0
+    }
+}
+
+impl<B: Bits> fmt::Display for ReservedBitsError<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "reserved bits are set: {:?}", self.0)
+    }
+}
+
 mod compile_tests {
     //! ```compile_fail
     //! use drone_core::reg::prelude::*;
@@ -667,6 +996,32 @@ mod compile_tests {
     //! }
     //! ```
     //!
+    //! ```compile_fail
+    //! use drone_core::{reg::prelude::*, token::Token};
+    //! drone_core::reg! {
+    //!     pub mod TST TST_WO_REG;
+    //!     0xDEAD_BEEF 0x20 0xBEEF_CACE WReg WoReg;
+    //!     TST_BIT { 0 1 WWRegField WoWRegField }
+    //! }
+    //! fn main() {
+    //!     let reg = unsafe { tst_tst_wo_reg::Reg::<Urt>::take() };
+    //!     reg.load();
+    //! }
+    //! ```
+    //!
+    //! ```no_run
+    //! use drone_core::{reg::prelude::*, token::Token};
+    //! drone_core::reg! {
+    //!     pub mod TST TST_WO_REG;
+    //!     0xDEAD_BEEF 0x20 0xBEEF_CACE WReg WoReg;
+    //!     TST_BIT { 0 1 WWRegField WoWRegField }
+    //! }
+    //! fn main() {
+    //!     let mut reg = unsafe { tst_tst_wo_reg::Reg::<Urt>::take() };
+    //!     reg.store(|r| r.set_tst_bit());
+    //! }
+    //! ```
+    //!
     //! ```
     //! use drone_core::reg::prelude::*;
     //! drone_core::reg! {
@@ -750,4 +1105,53 @@ mod compile_tests {
     //! reg_tokens1!(struct Regs1;);
     //! reg_tokens2!(struct Regs2;);
     //! ```
+    //!
+    //! ```compile_fail
+    //! use drone_core::reg::prelude::*;
+    //! drone_core::reg! {
+    //!     pub mod FOO BAR;
+    //!     0xDEAD_BEEF 0x20 0xBEEF_CACE RReg WReg;
+    //!     BAZ { 0 1 RRRegField WWRegField W1cRegField }
+    //! }
+    //! fn main() {}
+    //! ```
+    //!
+    //! ```
+    //! use drone_core::reg::prelude::*;
+    //! drone_core::reg! {
+    //!     pub mod FOO BAR;
+    //!     0xDEAD_BEEF 0x20 0xBEEF_CACE RReg WReg;
+    //!     BAZ { 0 1 RRRegField WWRegField }
+    //!     QUX { 1 3 RRRegField WWRegField }
+    //! }
+    //! fn main() {
+    //!     let _ = foo_bar::Reg::<Srt>::test_and_set_baz;
+    //!     let _ = foo_bar::Reg::<Crt>::test_and_set_baz;
+    //! }
+    //! ```
+    //!
+    //! ```compile_fail
+    //! use drone_core::reg::prelude::*;
+    //! drone_core::reg! {
+    //!     pub mod FOO BAR;
+    //!     0xDEAD_BEEF 0x20 0xBEEF_CACE RReg WReg;
+    //!     BAZ { 0 1 RRRegField WWRegField }
+    //!     QUX { 1 3 RRRegField WWRegField }
+    //! }
+    //! fn main() {
+    //!     let _ = foo_bar::Reg::<Crt>::test_and_set_qux;
+    //! }
+    //! ```
+    //!
+    //! ```compile_fail
+    //! use drone_core::reg::prelude::*;
+    //! drone_core::reg! {
+    //!     pub mod FOO BAR;
+    //!     0xDEAD_BEEF 0x20 0xBEEF_CACE RReg WReg;
+    //!     BAZ { 0 1 RRRegField WWRegField }
+    //! }
+    //! fn main() {
+    //!     let _ = foo_bar::Reg::<Urt>::test_and_set_baz;
+    //! }
+    //! ```
 }
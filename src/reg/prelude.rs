@@ -10,9 +10,9 @@
 
 #[doc(no_inline)]
 pub use crate::reg::{
-    field::{RRRegField, RegField, RoRRegField, WWRegField, WoWRegField},
+    field::{RRRegField, RegField, RoRRegField, W1cRegField, WWRegField, WoWRegField},
     tag::{Crt, RegAtomic, RegOwned, RegTag, Srt, Urt},
-    RReg, Reg, RegHold, RoReg, WReg, WoReg,
+    AtomicReg, RReg, Reg, RegHold, RoReg, WReg, WoReg,
 };
 
 #[doc(no_inline)]
@@ -22,5 +22,5 @@ pub use crate::reg::{
         WWRegFieldBit as _, WWRegFieldBits as _, WoWoRegField as _, WoWoRegFieldBit as _,
         WoWoRegFieldBits as _,
     },
-    RegRef as _, RwRegUnsync as _, WRegAtomic as _, WRegUnsync as _,
+    RegRef as _, RwRegAtomicBit as _, RwRegUnsync as _, WRegAtomic as _, WRegUnsync as _,
 };
@@ -22,22 +22,47 @@
 //!     // Path to the array of threads.
 //!     use THREADS;
 //!
-//!     /// The thread object.
+//!     // Optional priority of the threads, which can be read back with
+//!     // `Thread::PRIORITY`. Defaults to `thr::BASE_PRIORITY`.
+//!     priority: u8 = 1;
+//!
+//!     // Optional software interrupt index of the first thread, which can be
+//!     // read back with `Thr::SWI`. The thread at position `i` within the
+//!     // threads array uses `SWI + i`. Only with this option the thread object
+//!     // implements `thr::Pend`, which triggers its software interrupt, so
+//!     // threads of different types can be pended through `&dyn Pend`.
+//!     swi: u8 = 3;
+//!
+//!     /// The thread object. Its name is available as `Thr::NAME`.
+//!     // `Debug` derive is optional. It prints the fields of the thread object
+//!     // along with whether any fibers are attached. The number of fibers is
+//!     // not printed, because counting them could race with a drain.
+//!     #[derive(Debug)]
 //!     pub struct Thr {
 //!         // You can add your own fields to the thread object. These fields will be
 //!         // accessible through `to_thr` method of thread tokens. The types of
-//!         // these fields should be `Sync`.
-//!         pub foo: bool = false;
+//!         // these fields should be `Sync`. Note that the initializer uses the
+//!         // special `index` variable, that has the value of the position of the
+//!         // thread within the threads array. The initializer can be any constant
+//!         // expression, including `const fn` calls.
+//!         pub foo: bool = index % 2 == 0;
+//!         // An integer field marked with `#[atomic]` is stored as the matching
+//!         // atomic type, and gets `load_qux`, `store_qux`, and `fetch_add_qux`
+//!         // methods. 64-bit integers are not supported, as not every target
+//!         // has 64-bit atomics.
+//!         #[atomic]
+//!         pub qux: u32 = 0;
 //!     }
 //!
 //!     // This is a part of `Thr` that can be accessed with `thr::local` function.
 //!     /// The thread-local storage.
 //!     pub struct ThrLocal {
 //!         // You can add your own fields here with the same syntax as above.
-//!         // Note that the initializer uses the special `index` variable, that
-//!         // has the value of the position of the thread within the threads array.
 //!         // The types of these fields shouldn't necessarily be `Sync`.
 //!         pub bar: usize = index;
+//!         // Private fields are accessible through generated `baz` and unsafe
+//!         // `baz_mut` methods.
+//!         baz: u32 = 0;
 //!     }
 //! }
 //!
@@ -61,18 +86,40 @@ use crate::{
     fib::{Chain, FiberRoot},
     token::Token,
 };
+use core::slice;
+
+extern "C" {
+    fn drone_thr_pend_swi(swi: u8);
+}
+
+/// The priority of threads declared without an explicit priority.
+pub const BASE_PRIORITY: u8 = 0;
 
 /// Generic thread.
 pub trait Thread: Sized + Sync + 'static {
     /// The thread-local storage.
     type Local: ThreadLocal;
 
+    /// The priority of the thread.
+    ///
+    /// It is not used by Drone itself, but can be read by a scheduler to order
+    /// threads. Defaults to [`BASE_PRIORITY`].
+    const PRIORITY: u8 = BASE_PRIORITY;
+
     /// Returns a pointer to the first thread in the thread array.
     fn first() -> *const Self;
 
     /// Returns a reference to the fiber chain.
     fn fib_chain(&self) -> &Chain;
 
+    /// Adds the fiber `fib` to the fiber chain.
+    ///
+    /// This is a shortcut for `self.fib_chain().add(fib)`.
+    #[inline]
+    fn add_fib<F: FiberRoot>(&self, fib: F) {
+        self.fib_chain().add(fib);
+    }
+
     /// Returns a reference to the thread-local storage of the thread.
     ///
     /// [`local`] function should be used instead of this method.
@@ -84,6 +131,16 @@ pub trait Thread: Sized + Sync + 'static {
     unsafe fn local(&self) -> &Self::Local;
 }
 
+/// A thread type, which instances can be enumerated.
+///
+/// This trait is implemented by [`thr!`](crate::thr!) for each thread type. It
+/// is separate from [`Thread`], so manual [`Thread`] implementations don't have
+/// to provide it.
+pub trait ThreadArray: Thread {
+    /// Returns an iterator over all threads in the thread array.
+    fn all() -> slice::Iter<'static, Self>;
+}
+
 /// Generic thread-local storage.
 pub trait ThreadLocal: Sized + 'static {
     /// Returns a reference to the task context cell.
@@ -97,6 +154,38 @@ pub trait ThreadLocal: Sized + 'static {
     fn preempted(&self) -> &PreemptedCell;
 }
 
+/// A thread, which can be requested to run uniformly.
+///
+/// This trait is implemented by [`thr!`](crate::thr!) for each thread type
+/// declared with the `swi` option, by triggering the software interrupt of the
+/// thread with [`pend_swi`]. Unlike
+/// [`Thread`], it is object-safe, so a scheduler can store `&dyn Pend` for
+/// threads of different types. It is used by
+/// [`fib::new_bridge`](crate::fib::new_bridge).
+pub trait Pend: Sync {
+    /// Requests the thread to run.
+    ///
+    /// This method may be called from any thread.
+    fn pend(&self);
+}
+
+/// Triggers the software interrupt `swi`.
+///
+/// Triggering an interrupt depends on the target architecture, so it is not
+/// implemented here. Instead it is forwarded to the following function, which
+/// must be defined by the platform crate, e.g. by setting the pending bit of
+/// the interrupt. The symbol is required as soon as this function is used,
+/// e.g. by a thread declared with the `swi` option.
+///
+/// ```ignore
+/// #[no_mangle]
+/// extern "C" fn drone_thr_pend_swi(swi: u8);
+/// ```
+#[inline]
+pub fn pend_swi(swi: u8) {
+    unsafe { drone_thr_pend_swi(swi) };
+}
+
 /// The base trait for a thread token.
 ///
 /// # Safety
@@ -125,7 +214,7 @@ where
     /// Adds the fiber `fib` to the fiber chain.
     #[inline]
     fn add_fib<F: FiberRoot>(self, fib: F) {
-        self.to_thr().fib_chain().add(fib);
+        self.to_thr().add_fib(fib);
     }
 
     /// Returns `true` if the fiber chain is empty.
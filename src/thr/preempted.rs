@@ -2,14 +2,28 @@ use crate::thr::Thread;
 use core::cell::Cell;
 
 static mut CURRENT: usize = 0;
+static mut DEPTH: usize = 0;
 
 /// Thread-local previous thread index cell.
-pub struct PreemptedCell(Cell<usize>);
+pub struct PreemptedCell {
+    prev: Cell<usize>,
+    depth: Cell<usize>,
+}
 
 impl PreemptedCell {
     /// Creates a new `PreemptedCell`.
     pub const fn new() -> Self {
-        Self(Cell::new(0))
+        Self { prev: Cell::new(0), depth: Cell::new(0) }
+    }
+
+    /// Returns the preemption nesting depth of the thread.
+    ///
+    /// The value is `1` for a thread preempting the main program, `2` for a
+    /// thread preempting that thread, and so on. It is `0` when the thread is
+    /// not running.
+    #[inline]
+    pub fn depth(&self) -> usize {
+        self.depth.get()
     }
 }
 
@@ -23,8 +37,12 @@ pub fn local<T: Thread>() -> &'static T::Local {
 }
 
 pub(crate) unsafe fn preempt(preempted: &PreemptedCell, thr_num: usize, f: impl FnOnce()) {
-    preempted.0.set(CURRENT);
+    preempted.prev.set(CURRENT);
     CURRENT = thr_num;
+    DEPTH += 1;
+    let depth = preempted.depth.replace(DEPTH);
     f();
-    CURRENT = preempted.0.get();
+    preempted.depth.set(depth);
+    DEPTH -= 1;
+    CURRENT = preempted.prev.get();
 }
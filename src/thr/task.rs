@@ -1,7 +1,10 @@
 use core::{cell::Cell, mem::transmute, ptr::NonNull, task::Context};
 
 /// Thread-local task context cell.
-pub struct TaskCell(Cell<TaskContext>);
+pub struct TaskCell {
+    cx: Cell<TaskContext>,
+    task: Cell<Option<*const ()>>,
+}
 
 type TaskContext = Option<NonNull<Context<'static>>>;
 
@@ -10,7 +13,32 @@ struct ResetContext<'a>(TaskContext, &'a Cell<TaskContext>);
 impl TaskCell {
     /// Creates a new task context cell.
     pub const fn new() -> Self {
-        Self(Cell::new(None))
+        Self { cx: Cell::new(None), task: Cell::new(None) }
+    }
+
+    /// Associates the task pointer `task` with the thread.
+    ///
+    /// The pointer is opaque to Drone and can be used by a custom executor to
+    /// park a task on the thread. Only the owning thread context may call this
+    /// method.
+    #[inline]
+    pub fn set(&self, task: *const ()) {
+        self.task.set(Some(task));
+    }
+
+    /// Returns the task pointer previously associated with
+    /// [`TaskCell::set`], or `None` if there is none.
+    #[inline]
+    pub fn get(&self) -> Option<*const ()> {
+        self.task.get()
+    }
+
+    /// Removes the task pointer associated with the thread.
+    ///
+    /// Only the owning thread context may call this method.
+    #[inline]
+    pub fn clear(&self) {
+        self.task.set(None);
     }
 
     pub(crate) fn set_context<F, R>(&self, cx: &mut Context<'_>, f: F) -> R
@@ -18,8 +46,8 @@ impl TaskCell {
         F: FnOnce() -> R,
     {
         let cx = unsafe { transmute::<&mut Context<'_>, &mut Context<'static>>(cx) };
-        let prev_cx = self.0.replace(Some(NonNull::from(cx)));
-        let _reset = ResetContext(prev_cx, &self.0);
+        let prev_cx = self.cx.replace(Some(NonNull::from(cx)));
+        let _reset = ResetContext(prev_cx, &self.cx);
         f()
     }
 
@@ -27,8 +55,8 @@ impl TaskCell {
     where
         F: FnOnce(&mut Context<'_>) -> R,
     {
-        let cx = self.0.replace(None);
-        let _reset = ResetContext(cx, &self.0);
+        let cx = self.cx.replace(None);
+        let _reset = ResetContext(cx, &self.cx);
         f(unsafe { cx.expect("not an async context").as_mut() })
     }
 }
@@ -38,3 +66,24 @@ impl<'a> Drop for ResetContext<'a> {
         self.1.set(self.0);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::TaskCell;
+
+    #[test]
+    fn get_before_set() {
+        let cell = TaskCell::new();
+        assert_eq!(cell.get(), None);
+    }
+
+    #[test]
+    fn set_then_get() {
+        let task = 0_u32;
+        let cell = TaskCell::new();
+        cell.set(&task as *const u32 as *const ());
+        assert_eq!(cell.get(), Some(&task as *const u32 as *const ()));
+        cell.clear();
+        assert_eq!(cell.get(), None);
+    }
+}
@@ -19,6 +19,11 @@
 //! caller responsibility to ensure that at most one instance of the type ever
 //! exists.
 //!
+//! The trait is intentionally not sealed. Token types are generated by macros
+//! like [`reg!`](crate::reg!) and [`thr!`](crate::thr!) inside downstream
+//! crates, and platform crates define their own tokens. The `unsafe impl`
+//! requirement is what prevents forging a token by accident.
+//!
 //! Tokens are often nested to minimize the usage of `unsafe`
 //! [`Token::take`](token::Token::take) constructor. It is supposed to
 //! instantiate all needed tokens at the very beginning of the program and pass
@@ -89,6 +94,28 @@
 //! }
 //! ```
 //!
+//! Simple tokens also implement [`CheckedToken`](token::CheckedToken), which
+//! allows to acquire them safely with a run-time check. This is useful to catch
+//! double-ownership bugs during bring-up, at the cost of one static flag per
+//! token. The flag is cleared only by `release`, a dropped token stays taken.
+//! The check doesn't account for the instances created with the `unsafe`
+//! `take`, so a token should be acquired in only one of these ways:
+//!
+//! ```
+//! use drone_core::token::{simple_token, CheckedToken};
+//!
+//! simple_token! {
+//!     /// The token for Baz initializer.
+//!     pub struct BazInitToken;
+//! }
+//!
+//! let baz_init = BazInitToken::try_take().unwrap();
+//! assert!(BazInitToken::try_take().is_err());
+//! // Hand the token over deliberately.
+//! baz_init.release();
+//! assert!(BazInitToken::try_take().is_ok());
+//! ```
+//!
 //! # Static Tokens
 //!
 //! Mutable statics are unsafe in Rust. One way to make them safe is to use
@@ -143,6 +170,8 @@
 //! }
 //! ```
 
+use core::fmt;
+
 /// Defines a new simple [`Token`].
 ///
 /// See [the module-level documentation](self) for details.
@@ -216,6 +245,52 @@ pub unsafe trait StaticToken: Token + Sized + Send + 'static {
     fn into_static(self) -> &'static mut Self::Target;
 }
 
+/// A [`Token`] which can be acquired safely, with a run-time check.
+///
+/// Implemented by the tokens defined with [`simple_token!`]. Each such token
+/// has a static flag, which is set by [`try_take`](CheckedToken::try_take) and
+/// cleared by [`release`](CheckedToken::release). Dropping the token doesn't
+/// clear the flag, so a token dropped without calling
+/// [`release`](CheckedToken::release) stays taken forever.
+///
+/// # Safety
+///
+/// [`try_take`](CheckedToken::try_take) must not return an instance while
+/// another instance returned by it is alive.
+pub unsafe trait CheckedToken: Token {
+    /// Creates the token instance, unless it is already taken.
+    ///
+    /// Only the instances created by this method are tracked. An instance
+    /// created with the `unsafe` [`Token::take`] doesn't affect the result, so
+    /// mixing the two methods for the same token breaks the single-instance
+    /// contract of [`Token`]. A token should be acquired either only with this
+    /// method, or only with [`Token::take`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an instance created by this method is still alive.
+    fn try_take() -> Result<Self, AlreadyTakenError>;
+
+    /// Drops the token instance, allowing [`try_take`](CheckedToken::try_take)
+    /// to succeed again.
+    ///
+    /// This is the only way to clear the flag. A plain drop of the instance
+    /// keeps the token taken.
+    fn release(self);
+}
+
+/// An error indicating that a [`CheckedToken`] is already taken.
+///
+/// This error is returned by [`CheckedToken::try_take`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct AlreadyTakenError;
+
+impl fmt::Display for AlreadyTakenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "token is already taken")
+    }
+}
+
 mod compile_tests {
     //! ```compile_fail
     //! drone_core::token::simple_token!(struct Foo);
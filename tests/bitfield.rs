@@ -8,6 +8,9 @@ use drone_core::bitfield::Bitfield;
 )]
 pub struct Byte(u8);
 
+#[derive(Bitfield, Copy, Clone)]
+pub struct Word(u32);
+
 #[test]
 fn read_bit() {
     let x = Byte(0b1010_1010);
@@ -115,3 +118,30 @@ fn write_bits() {
     unsafe { x.write_bits(0, 8, 0b1111_1111) };
     assert_eq!(x.bits(), 0b1111_1111);
 }
+
+#[test]
+fn set_bits() {
+    assert_eq!(Word(0).set_bits().collect::<Vec<_>>(), []);
+    assert_eq!(Word(0x1).set_bits().collect::<Vec<_>>(), [0]);
+    assert_eq!(Word(0x8000_0000).set_bits().collect::<Vec<_>>(), [31]);
+    assert_eq!(Word(0x8010_0206).set_bits().collect::<Vec<_>>(), [1, 2, 9, 20, 31]);
+    assert_eq!(Byte(0xFF).set_bits().collect::<Vec<_>>(), [0, 1, 2, 3, 4, 5, 6, 7]);
+}
+
+#[test]
+fn set_clear_mask() {
+    let mut x = Byte(0b1010_0000);
+    x.set_mask(0b0000_1111);
+    assert_eq!(x.bits(), 0b1010_1111);
+    x.clear_mask(0b0000_1111);
+    assert_eq!(x.bits(), 0b1010_0000);
+}
+
+#[test]
+fn toggle_mask() {
+    let mut x = Byte(0b1010_1010);
+    x.toggle_mask(0b1111_0000);
+    assert_eq!(x.bits(), 0b0101_1010);
+    x.toggle_mask(0b1111_0000);
+    assert_eq!(x.bits(), 0b1010_1010);
+}
@@ -0,0 +1,12 @@
+use drone_core::{reg::prelude::*, token::Token};
+
+drone_core::reg! {
+    pub mod FOO BAR;
+    0xDEAD_BEEF 0x20 0xBEEF_CACE RReg WReg AtomicReg;
+    BAZ { 0 1 RRRegField WWRegField }
+}
+
+fn main() {
+    let reg = unsafe { foo_bar::Reg::<Urt>::take() };
+    RReg::load_val(&reg);
+}
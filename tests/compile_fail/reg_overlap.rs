@@ -0,0 +1,15 @@
+drone_core::reg! {
+    pub mod FOO BAR;
+    0xDEAD_BEEF 0x20 0xBEEF_CACE RReg WReg;
+    BUSY { 0 1 RRRegField RoRRegField }
+    READY { 0 2 RRRegField RoRRegField }
+}
+
+drone_core::reg! {
+    pub mod FOO BAZ;
+    0xDEAD_BEEF 0x20 0xBEEF_CACE RReg WReg;
+    START { 0 1 WWRegField WoWRegField }
+    CLEAR { 0 1 RRRegField W1cRegField }
+}
+
+fn main() {}
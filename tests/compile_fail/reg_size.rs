@@ -0,0 +1,12 @@
+drone_core::reg! {
+    pub mod FOO BAR;
+    0xDEAD_BEEF 0x10 0x0000 RReg WReg;
+    BAZ { 12 8 RRRegField WWRegField }
+}
+
+drone_core::reg! {
+    pub mod FOO BAZ;
+    0xDEAD_BEEF 0x18 0x0000 RReg WReg;
+}
+
+fn main() {}
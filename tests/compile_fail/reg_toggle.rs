@@ -0,0 +1,13 @@
+use drone_core::{reg::prelude::*, token::Token};
+
+drone_core::reg! {
+    pub mod FOO BAR;
+    0xDEAD_BEEF 0x20 0xBEEF_CACE RReg WReg;
+    TEST_BIT { 0 1 RRRegField WWRegField }
+    TEST_BITS { 1 3 RRRegField WWRegField }
+}
+
+fn main() {
+    let mut reg = unsafe { foo_bar::Reg::<Urt>::take() };
+    reg.toggle_test_bits();
+}
@@ -0,0 +1,7 @@
+drone_core::reg! {
+    pub mod FOO BAR;
+    0xDEAD_BEEF 0x20 0xBEEF_CACE WReg WoReg;
+    BAZ { 0 1 RRRegField WWRegField }
+}
+
+fn main() {}
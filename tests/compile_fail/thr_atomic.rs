@@ -0,0 +1,22 @@
+#![feature(const_fn)]
+
+use drone_core::thr;
+
+struct Counter(u32);
+
+static mut THREADS: [Thr; 1] = [Thr::new(0)];
+
+thr! {
+    use THREADS;
+
+    pub struct Thr {
+        #[atomic]
+        pub counter: Counter = Counter(0);
+        #[atomic]
+        pub ticks: u64 = 0;
+    }
+
+    pub struct ThrLocal {}
+}
+
+fn main() {}
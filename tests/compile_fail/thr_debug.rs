@@ -0,0 +1,20 @@
+#![feature(const_fn)]
+
+use drone_core::thr;
+
+struct NotDebug;
+
+static mut THREADS: [Thr; 1] = [Thr::new(0)];
+
+thr! {
+    use THREADS;
+
+    #[derive(Debug)]
+    pub struct Thr {
+        pub foo: NotDebug = NotDebug;
+    }
+
+    pub struct ThrLocal {}
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+#![feature(const_fn)]
+
+use drone_core::thr::{self, Pend};
+
+static mut THREADS: [Thr; 1] = [Thr::new(0)];
+
+thr! {
+    use THREADS;
+    pub struct Thr {}
+    pub struct ThrLocal {}
+}
+
+fn main() {
+    Pend::pend(unsafe { &THREADS[0] });
+}
@@ -0,0 +1,17 @@
+#![feature(const_fn)]
+
+use drone_core::thr;
+
+static mut THREADS: [Thr; 1] = [Thr::new(0)];
+
+thr! {
+    use THREADS;
+
+    priority: u16 = 1;
+
+    pub struct Thr {}
+
+    pub struct ThrLocal {}
+}
+
+fn main() {}
@@ -0,0 +1,590 @@
+#![feature(generators)]
+
+use drone_core::{
+    fib::{
+        self, Chain, Complete, DrainPolicy, Fiber, FiberExt, FiberState, SchedChain, Selected,
+        TimedOut, Yielded,
+    },
+    thr::Pend,
+};
+use std::{
+    panic,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering::*},
+        Arc, Mutex,
+    },
+};
+
+#[test]
+fn chain_len() {
+    let chain = Chain::new();
+    assert!(chain.is_empty());
+    assert_eq!(unsafe { chain.len() }, 0);
+    chain.add(fib::new(|| {
+        loop {
+            yield;
+        }
+    }));
+    assert!(!chain.is_empty());
+    assert_eq!(unsafe { chain.len() }, 1);
+    chain.add(fib::new(|| {
+        yield;
+    }));
+    chain.add(fib::new(|| {
+        loop {
+            yield;
+        }
+    }));
+    assert_eq!(unsafe { chain.len() }, 3);
+    unsafe { chain.drain() };
+    assert_eq!(unsafe { chain.len() }, 3);
+    unsafe { chain.drain() };
+    assert_eq!(unsafe { chain.len() }, 2);
+}
+
+#[test]
+fn handle_cancel_before_run() {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let chain = Chain::new();
+    let handle = chain.add_with_handle(fib::new({
+        let counter = Arc::clone(&counter);
+        move || loop {
+            counter.fetch_add(1, Relaxed);
+            yield;
+        }
+    }));
+    assert!(handle.cancel());
+    unsafe { chain.drain() };
+    assert_eq!(counter.load(Relaxed), 0);
+    assert!(chain.is_empty());
+    assert_eq!(Arc::strong_count(&counter), 1);
+}
+
+#[test]
+fn handle_cancel_after_completion() {
+    let chain = Chain::new();
+    let handle = chain.add_with_handle(fib::new(|| {
+        yield;
+    }));
+    unsafe { chain.drain() };
+    unsafe { chain.drain() };
+    assert!(chain.is_empty());
+    assert!(!handle.cancel());
+}
+
+#[test]
+fn handle_cancel_twice() {
+    let chain = Chain::new();
+    let handle = chain.add_with_handle(fib::new(|| {
+        loop {
+            yield;
+        }
+    }));
+    assert!(handle.cancel());
+    assert!(!handle.cancel());
+    unsafe { chain.drain() };
+    assert!(chain.is_empty());
+    assert!(!handle.cancel());
+}
+
+#[test]
+fn generator_progress() {
+    let chain = Chain::new();
+    let (fib, progress) = fib::new_generator(|| {
+        yield 1;
+        yield 2;
+        yield 3;
+        99
+    });
+    chain.add(fib);
+    assert_eq!(progress.take(), None);
+    for i in 1..=3 {
+        unsafe { chain.drain() };
+        assert_eq!(progress.take(), Some(Yielded(i)));
+        assert_eq!(progress.take(), None);
+    }
+    unsafe { chain.drain() };
+    assert_eq!(progress.take(), Some(Complete(99)));
+    assert!(chain.is_empty());
+}
+
+fn countdown(count: usize) -> usize {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let chain = Chain::new();
+    chain.add(fib::new_countdown(count, {
+        let counter = Arc::clone(&counter);
+        move || {
+            counter.fetch_add(1, Relaxed);
+        }
+    }));
+    for _ in 0..5 {
+        unsafe { chain.drain() };
+    }
+    assert!(chain.is_empty());
+    counter.load(Relaxed)
+}
+
+#[test]
+fn countdown_zero() {
+    assert_eq!(countdown(0), 0);
+}
+
+#[test]
+fn countdown_one() {
+    assert_eq!(countdown(1), 1);
+}
+
+#[test]
+fn countdown_three() {
+    assert_eq!(countdown(3), 3);
+}
+
+#[test]
+fn until_third_poll() {
+    let polls = Arc::new(AtomicUsize::new(0));
+    let chain = Chain::new();
+    chain.add(fib::new_until({
+        let polls = Arc::clone(&polls);
+        move || polls.fetch_add(1, Relaxed) == 2
+    }));
+    assert_eq!(unsafe { chain.drain_once() }, 1);
+    assert_eq!(unsafe { chain.drain_once() }, 1);
+    assert_eq!(unsafe { chain.drain_once() }, 0);
+    assert_eq!(polls.load(Relaxed), 3);
+}
+
+#[test]
+fn once_detaches() {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let chain = Chain::new();
+    chain.add(fib::new_once({
+        let counter = Arc::clone(&counter);
+        move || {
+            counter.fetch_add(1, Relaxed);
+        }
+    }));
+    assert!(!chain.is_empty());
+    assert_eq!(unsafe { chain.drain_once() }, 0);
+    assert_eq!(counter.load(Relaxed), 1);
+    assert!(chain.is_empty());
+    assert_eq!(unsafe { chain.drain_once() }, 0);
+    assert_eq!(counter.load(Relaxed), 1);
+}
+
+#[test]
+fn chain_drain_once() {
+    let chain = Chain::new();
+    assert_eq!(unsafe { chain.drain_once() }, 0);
+    chain.add(fib::new(|| {
+        loop {
+            yield;
+        }
+    }));
+    chain.add(fib::new_once(|| {}));
+    chain.add(fib::new_countdown(2, || {}));
+    chain.add(fib::new(|| {
+        yield;
+        yield;
+        yield;
+    }));
+    assert_eq!(unsafe { chain.drain_once() }, 3);
+    assert_eq!(unsafe { chain.drain_once() }, 2);
+    assert_eq!(unsafe { chain.drain_once() }, 2);
+    assert_eq!(unsafe { chain.drain_once() }, 1);
+    assert_eq!(unsafe { chain.drain_once() }, 1);
+}
+
+#[test]
+fn chain_drain_once_after_panic() {
+    let chain = Chain::new();
+    let mut panicked = false;
+    chain.add(fib::new_fn(move || {
+        if !panicked {
+            panicked = true;
+            panic!();
+        }
+        Complete(())
+    }));
+    assert!(
+        panic::catch_unwind(panic::AssertUnwindSafe(|| unsafe { chain.drain_once() })).is_err()
+    );
+    assert_eq!(unsafe { chain.drain_once() }, 0);
+}
+
+#[test]
+fn chain_add_prioritized() {
+    let order = Arc::new(Mutex::new(Vec::new()));
+    let chain = SchedChain::new();
+    for (id, &priority) in [1, 0, 3, 2, 0, 3].iter().enumerate() {
+        let order = Arc::clone(&order);
+        let fib = fib::new_fn(move || -> FiberState<(), ()> {
+            order.lock().unwrap().push((priority, id));
+            Complete(())
+        });
+        if priority == 0 {
+            chain.add(fib);
+        } else {
+            unsafe { chain.add_prioritized(fib, priority) };
+        }
+    }
+    assert_eq!(unsafe { chain.drain_once() }, 0);
+    assert_eq!(*order.lock().unwrap(), [(3, 2), (3, 5), (2, 3), (1, 0), (0, 4), (0, 1)]);
+}
+
+#[test]
+fn chain_suspend() {
+    static CHAIN: SchedChain = SchedChain::new();
+    static PENDS: AtomicUsize = AtomicUsize::new(0);
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    struct Thr;
+    impl Pend for Thr {
+        fn pend(&self) {
+            PENDS.fetch_add(1, SeqCst);
+            unsafe { CHAIN.drain() };
+        }
+    }
+    CHAIN.add(fib::new_fn(|| -> FiberState<(), ()> {
+        COUNTER.fetch_add(1, SeqCst);
+        Yielded(())
+    }));
+    let guard = CHAIN.suspend(&Thr);
+    assert_eq!(unsafe { CHAIN.drain_once() }, 1);
+    unsafe { CHAIN.drain() };
+    unsafe { CHAIN.drain_round_robin() };
+    assert_eq!(COUNTER.load(SeqCst), 0);
+    assert_eq!(PENDS.load(SeqCst), 0);
+    drop(guard);
+    assert_eq!(PENDS.load(SeqCst), 1);
+    assert_eq!(COUNTER.load(SeqCst), 1);
+    let outer = CHAIN.suspend(&Thr);
+    let inner = CHAIN.suspend(&Thr);
+    unsafe { CHAIN.drain() };
+    drop(inner);
+    assert_eq!(PENDS.load(SeqCst), 1);
+    drop(outer);
+    assert_eq!(PENDS.load(SeqCst), 2);
+    assert_eq!(COUNTER.load(SeqCst), 2);
+    drop(CHAIN.suspend(&Thr));
+    assert_eq!(PENDS.load(SeqCst), 2);
+    assert_eq!(COUNTER.load(SeqCst), 2);
+}
+
+#[test]
+fn chain_yield_observer() {
+    let yields = Arc::new(Mutex::new(Vec::new()));
+    let chain = SchedChain::new();
+    chain.add_observed(fib::new(|| {
+        yield 1_u32;
+        yield 2;
+    }));
+    chain.add_observed(fib::new(|| {
+        yield 10_u32;
+    }));
+    chain.add(fib::new(|| {
+        yield;
+    }));
+    {
+        let yields = Arc::clone(&yields);
+        unsafe {
+            chain.set_yield_observer(move |value| {
+                yields.lock().unwrap().push(value.downcast_ref::<u32>().copied());
+            });
+        }
+    }
+    assert_eq!(unsafe { chain.drain_once() }, 3);
+    assert_eq!(*yields.lock().unwrap(), [None, Some(10), Some(1)]);
+    unsafe { chain.remove_yield_observer() };
+    assert_eq!(unsafe { chain.drain_once() }, 1);
+    assert_eq!(yields.lock().unwrap().len(), 3);
+}
+
+#[test]
+fn chain_yield_observer_batch() {
+    let batches = Arc::new(Mutex::new(Vec::new()));
+    let chain = SchedChain::new();
+    chain.add_observed(fib::new(|| {
+        yield vec![3_u8, 1, 2];
+        yield Vec::new();
+    }));
+    {
+        let batches = Arc::clone(&batches);
+        unsafe {
+            chain.set_yield_observer(move |value| {
+                batches.lock().unwrap().push(value.downcast_ref::<Vec<u8>>().unwrap().clone());
+            });
+        }
+    }
+    assert_eq!(unsafe { chain.drain_once() }, 1);
+    assert_eq!(*batches.lock().unwrap(), [vec![3, 1, 2]]);
+    assert_eq!(unsafe { chain.drain_once() }, 1);
+    assert_eq!(*batches.lock().unwrap(), [vec![3, 1, 2], vec![]]);
+}
+
+#[test]
+fn chain_drain_round_robin() {
+    let order = Arc::new(Mutex::new(Vec::new()));
+    let chain = SchedChain::new();
+    assert_eq!(chain.policy(), DrainPolicy::Sequential);
+    chain.set_policy(DrainPolicy::RoundRobin);
+    assert_eq!(chain.policy(), DrainPolicy::RoundRobin);
+    for id in 0..3 {
+        let order = Arc::clone(&order);
+        chain.add(fib::new_fn(move || -> FiberState<(), ()> {
+            order.lock().unwrap().push(id);
+            Yielded(())
+        }));
+    }
+    for _ in 0..3 {
+        assert_eq!(unsafe { chain.drain_once() }, 3);
+    }
+    assert_eq!(*order.lock().unwrap(), [2, 1, 0, 1, 0, 2, 0, 2, 1]);
+}
+
+#[test]
+fn chain_drain_round_robin_removes_completed() {
+    let order = Arc::new(Mutex::new(Vec::new()));
+    let chain = SchedChain::new();
+    for id in 0..4 {
+        let order = Arc::clone(&order);
+        let mut count = 0;
+        chain.add(fib::new_fn(move || {
+            order.lock().unwrap().push(id);
+            count += 1;
+            if id % 2 == 0 && count == 2 { Complete(()) } else { Yielded(()) }
+        }));
+    }
+    unsafe { chain.drain_round_robin() };
+    unsafe { chain.drain_round_robin() };
+    assert_eq!(unsafe { chain.len() }, 2);
+    unsafe { chain.drain_round_robin() };
+    assert_eq!(*order.lock().unwrap(), [3, 2, 1, 0, 2, 1, 0, 3, 3, 1]);
+}
+
+#[test]
+fn counted_poll_count() {
+    let chain = Chain::new();
+    let (fib, count) = fib::new_counted(fib::new(|| {
+        for _ in 0..4 {
+            yield;
+        }
+    }));
+    chain.add(fib);
+    assert_eq!(count.poll_count(), 0);
+    for _ in 0..5 {
+        unsafe { chain.drain_once() };
+    }
+    assert!(chain.is_empty());
+    assert_eq!(count.poll_count(), 5);
+}
+
+fn timeout_polls(polls: usize) -> Vec<FiberState<(), Result<u8, TimedOut>>> {
+    let mut fib = fib::new(|| {
+        for _ in 0..3 {
+            yield;
+        }
+        4
+    })
+    .timeout_polls(polls);
+    let mut fib = unsafe { Pin::new_unchecked(&mut fib) };
+    let mut states = Vec::new();
+    loop {
+        let state = fib.as_mut().resume(());
+        states.push(state);
+        if state.is_complete() {
+            break states;
+        }
+    }
+}
+
+#[test]
+fn timeout_polls_inner_wins() {
+    assert_eq!(timeout_polls(4), [Yielded(()), Yielded(()), Yielded(()), Complete(Ok(4))]);
+}
+
+#[test]
+fn timeout_polls_timeout_wins() {
+    assert_eq!(timeout_polls(3), [Yielded(()), Yielded(()), Complete(Err(TimedOut))]);
+}
+
+#[test]
+fn then_same_slot() {
+    let result = Arc::new(AtomicUsize::new(0));
+    let chain = Chain::new();
+    chain.add(
+        fib::new(|| {
+            yield;
+            21
+        })
+        .then({
+            let result = Arc::clone(&result);
+            move |value| {
+                fib::new(move || {
+                    yield;
+                    result.store(value * 2, Relaxed);
+                })
+            }
+        }),
+    );
+    assert_eq!(unsafe { chain.drain_once() }, 1);
+    assert_eq!(unsafe { chain.drain_once() }, 1);
+    assert_eq!(result.load(Relaxed), 0);
+    assert_eq!(unsafe { chain.drain_once() }, 0);
+    assert_eq!(result.load(Relaxed), 42);
+}
+
+#[test]
+fn select_second_completes() {
+    let loser = Arc::new(());
+    let mut fib = fib::select(
+        fib::new_fn({
+            let loser = Arc::clone(&loser);
+            move || -> FiberState<(), ()> {
+                let _ = &loser;
+                Yielded(())
+            }
+        }),
+        fib::new(|| {
+            yield;
+            2
+        }),
+    );
+    let mut fib = unsafe { Pin::new_unchecked(&mut fib) };
+    assert_eq!(fib.as_mut().resume(()), Yielded(()));
+    assert_eq!(Arc::strong_count(&loser), 2);
+    assert_eq!(fib.as_mut().resume(()), Complete(Selected::B(2)));
+    assert_eq!(Arc::strong_count(&loser), 1);
+}
+
+#[test]
+fn select_both_complete() {
+    let mut fib = fib::select(
+        fib::new_fn(|| FiberState::<(), _>::Complete(1)),
+        fib::new_fn(|| FiberState::<(), _>::Complete(2)),
+    );
+    let mut fib = unsafe { Pin::new_unchecked(&mut fib) };
+    assert_eq!(fib.as_mut().resume(()), Complete(Selected::A(1)));
+}
+
+#[test]
+fn join_waits_for_both() {
+    let a_polls = Arc::new(AtomicUsize::new(0));
+    let mut fib = fib::join(
+        fib::new_fn({
+            let a_polls = Arc::clone(&a_polls);
+            move || -> FiberState<(), _> {
+                a_polls.fetch_add(1, Relaxed);
+                Complete('a')
+            }
+        }),
+        fib::new(|| {
+            yield;
+            yield;
+            'b'
+        }),
+    );
+    let mut fib = unsafe { Pin::new_unchecked(&mut fib) };
+    assert_eq!(fib.as_mut().resume(()), Yielded(()));
+    assert_eq!(fib.as_mut().resume(()), Yielded(()));
+    assert_eq!(fib.as_mut().resume(()), Complete(('a', 'b')));
+    assert_eq!(a_polls.load(Relaxed), 1);
+}
+
+#[test]
+fn cancellable_cleanup() {
+    let cleaned_up = Arc::new(AtomicUsize::new(0));
+    let (fib, cancel) = fib::new_cancellable({
+        let cleaned_up = Arc::clone(&cleaned_up);
+        move |cancel| {
+            move || {
+                loop {
+                    yield;
+                    if cancel.is_cancelled() {
+                        cleaned_up.fetch_add(1, Relaxed);
+                        return;
+                    }
+                }
+            }
+        }
+    });
+    let chain = Chain::new();
+    chain.add(fib);
+    assert_eq!(unsafe { chain.drain_once() }, 1);
+    assert_eq!(unsafe { chain.drain_once() }, 1);
+    assert!(!cancel.is_cancelled());
+    cancel.cancel();
+    assert_eq!(cleaned_up.load(Relaxed), 0);
+    assert_eq!(unsafe { chain.drain_once() }, 0);
+    assert_eq!(cleaned_up.load(Relaxed), 1);
+}
+
+#[test]
+fn map_yields() {
+    let mut fib = fib::new(|| {
+        yield 100;
+        yield 200;
+        4095
+    })
+    .map(|counts: u32| counts * 3300 / 4095);
+    let mut fib = unsafe { Pin::new_unchecked(&mut fib) };
+    assert_eq!(fib.as_mut().resume(()), Yielded(80));
+    assert_eq!(fib.as_mut().resume(()), Yielded(161));
+    assert_eq!(fib.as_mut().resume(()), Complete(4095));
+}
+
+#[test]
+fn map_in_chain() {
+    let millivolts = Arc::new(Mutex::new(Vec::new()));
+    let chain = Chain::new();
+    chain.add(
+        fib::new(|| {
+            yield 100;
+            yield 200;
+        })
+        .map({
+            let millivolts = Arc::clone(&millivolts);
+            move |counts: u32| millivolts.lock().unwrap().push(counts * 3300 / 4095)
+        }),
+    );
+    while unsafe { chain.drain_once() } > 0 {}
+    assert_eq!(*millivolts.lock().unwrap(), [80, 161]);
+}
+
+#[test]
+fn every_nth() {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let chain = Chain::new();
+    chain.add(fib::new_every(3, {
+        let counter = Arc::clone(&counter);
+        move || {
+            counter.fetch_add(1, Relaxed);
+        }
+    }));
+    for _ in 0..7 {
+        assert_eq!(unsafe { chain.drain_once() }, 1);
+    }
+    assert_eq!(counter.load(Relaxed), 2);
+}
+
+#[test]
+fn every_first() {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let chain = Chain::new();
+    chain.add(fib::new_every(1, {
+        let counter = Arc::clone(&counter);
+        move || {
+            counter.fetch_add(1, Relaxed);
+        }
+    }));
+    for _ in 0..3 {
+        unsafe { chain.drain_once() };
+    }
+    assert_eq!(counter.load(Relaxed), 3);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic]
+fn every_zero() {
+    fib::new_every(0, || {});
+}
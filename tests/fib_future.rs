@@ -0,0 +1,101 @@
+#![feature(const_fn)]
+
+use drone_core::{
+    fib,
+    thr::{prelude::*, Thread},
+    token::Token,
+};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::atomic::{AtomicUsize, Ordering::*},
+    task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+};
+
+use drone_core::thr;
+
+static mut THREADS: [Thr; 2] = [Thr::new(0), Thr::new(1)];
+
+static PENDS: [AtomicUsize; 2] = [AtomicUsize::new(0), AtomicUsize::new(0)];
+
+thr! {
+    use THREADS;
+    swi: u8 = 0;
+    pub struct Thr {}
+    pub struct ThrLocal {}
+}
+
+#[derive(Clone, Copy)]
+struct Thr1;
+
+unsafe impl Token for Thr1 {
+    unsafe fn take() -> Self {
+        Self
+    }
+}
+
+unsafe impl ThrToken for Thr1 {
+    type Thr = Thr;
+
+    const THR_NUM: usize = 1;
+}
+
+#[no_mangle]
+extern "C" fn drone_thr_pend_swi(swi: u8) {
+    PENDS[usize::from(swi)].fetch_add(1, SeqCst);
+}
+
+struct Counter(AtomicUsize);
+
+impl Counter {
+    fn to_waker(&'static self) -> Waker {
+        unsafe fn clone(counter: *const ()) -> RawWaker {
+            RawWaker::new(counter, &VTABLE)
+        }
+        unsafe fn wake(counter: *const ()) {
+            (*(counter as *const Counter)).0.fetch_add(1, SeqCst);
+        }
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake, drop);
+        unsafe { Waker::from_raw(RawWaker::new(self as *const _ as *const (), &VTABLE)) }
+    }
+}
+
+// Wakes itself on each poll until the countdown reaches zero.
+struct Countdown(usize);
+
+impl Future for Countdown {
+    type Output = usize;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<usize> {
+        self.0 -= 1;
+        if self.0 == 0 {
+            Poll::Ready(123)
+        } else {
+            cx.waker().clone().wake();
+            Poll::Pending
+        }
+    }
+}
+
+#[test]
+fn wakes_owning_thread() {
+    static COUNTER: Counter = Counter(AtomicUsize::new(0));
+    let waker = COUNTER.to_waker();
+    let mut cx = Context::from_waker(&waker);
+    unsafe {
+        let thr = Thr1::take();
+        let mut fut = thr.add_future(fib::new_future(thr.to_thr(), Countdown(3)));
+        assert_eq!(Pin::new(&mut fut).poll(&mut cx), Poll::Pending);
+        thr.to_thr().fib_chain().drain();
+        assert_eq!(PENDS[1].load(SeqCst), 1);
+        thr.to_thr().fib_chain().drain();
+        assert_eq!(PENDS[1].load(SeqCst), 2);
+        assert_eq!(Pin::new(&mut fut).poll(&mut cx), Poll::Pending);
+        thr.to_thr().fib_chain().drain();
+        assert_eq!(PENDS[1].load(SeqCst), 2);
+        assert_eq!(Pin::new(&mut fut).poll(&mut cx), Poll::Ready(123));
+        assert_eq!(COUNTER.0.load(SeqCst), 1);
+        assert_eq!(PENDS[0].load(SeqCst), 0);
+        assert!(thr.is_empty());
+    }
+}
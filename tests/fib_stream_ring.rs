@@ -0,0 +1,81 @@
+#![feature(const_fn)]
+#![feature(generators)]
+
+use drone_core::{
+    fib,
+    thr::{prelude::*, Thread},
+    token::Token,
+};
+use futures::Stream;
+use std::{
+    pin::Pin,
+    sync::atomic::{AtomicUsize, Ordering::*},
+    task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+};
+
+use drone_core::thr;
+
+static mut THREADS: [Thr; 1] = [Thr::new(0)];
+
+thr! {
+    use THREADS;
+    pub struct Thr {}
+    pub struct ThrLocal {}
+}
+
+#[derive(Clone, Copy)]
+struct Thr0;
+
+unsafe impl Token for Thr0 {
+    unsafe fn take() -> Self {
+        Self
+    }
+}
+
+unsafe impl ThrToken for Thr0 {
+    type Thr = Thr;
+
+    const THR_NUM: usize = 0;
+}
+
+struct Counter(AtomicUsize);
+
+impl Counter {
+    fn to_waker(&'static self) -> Waker {
+        unsafe fn clone(counter: *const ()) -> RawWaker {
+            RawWaker::new(counter, &VTABLE)
+        }
+        unsafe fn wake(counter: *const ()) {
+            (*(counter as *const Counter)).0.fetch_add(1, SeqCst);
+        }
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake, drop);
+        unsafe { Waker::from_raw(RawWaker::new(self as *const _ as *const (), &VTABLE)) }
+    }
+}
+
+#[test]
+fn overruns() {
+    static COUNTER: Counter = Counter(AtomicUsize::new(0));
+    let waker = COUNTER.to_waker();
+    let mut cx = Context::from_waker(&waker);
+    unsafe {
+        let thr = Thr0::take();
+        let mut stream = thr.add_stream_ring_skip(
+            3,
+            fib::new(|| {
+                for item in 1..=5 {
+                    yield Some(item);
+                }
+                None
+            }),
+        );
+        for _ in 0..6 {
+            thr.to_thr().fib_chain().drain();
+        }
+        assert_eq!(stream.overruns(), 2);
+        assert_eq!(Pin::new(&mut stream).poll_next(&mut cx), Poll::Ready(Some(1)));
+        assert_eq!(Pin::new(&mut stream).poll_next(&mut cx), Poll::Ready(Some(2)));
+        assert_eq!(Pin::new(&mut stream).poll_next(&mut cx), Poll::Ready(Some(3)));
+        assert_eq!(Pin::new(&mut stream).poll_next(&mut cx), Poll::Ready(None));
+    }
+}
@@ -1,7 +1,18 @@
 #![feature(proc_macro_hygiene)]
 
-use drone_core::{bitfield::Bitfield, reg::prelude::*, token::Token};
-use std::mem::size_of;
+use drone_core::{
+    bitfield::Bitfield,
+    reg::{field::RegFieldEnum, prelude::*, DynReg, Reg, RegRef},
+    token::Token,
+};
+use std::{
+    mem::size_of,
+    ptr,
+    sync::{
+        atomic::{AtomicBool, AtomicU32, Ordering},
+        Mutex,
+    },
+};
 
 use drone_core::reg;
 
@@ -14,8 +25,111 @@ reg! {
 
     0xDEAD_BEEF 0x20 0xBEEF_CACE RReg WReg;
 
+    /// Test bit doc attribute
     TEST_BIT { 0 1 RRRegField WWRegField }
+    /// Test bits doc attribute
+    #[doc = "test bits attribute"]
     TEST_BITS { 1 3 RRRegField WWRegField }
+    TEST_MODE { 4 2 RRRegField WWRegField } as Mode
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Mode {
+    Off,
+    Low,
+    High,
+    Max,
+}
+
+impl RegFieldEnum<u32> for Mode {
+    fn from_bits(bits: u32) -> Option<Self> {
+        match bits {
+            0 => Some(Self::Off),
+            1 => Some(Self::Low),
+            2 => Some(Self::High),
+            3 => Some(Self::Max),
+            _ => None,
+        }
+    }
+
+    fn fallback(_bits: u32) -> Self {
+        Self::Off
+    }
+
+    fn into_bits(self) -> u32 {
+        self as u32
+    }
+}
+
+reg! {
+    pub mod TEST_BLOCK TEST_STATUS;
+
+    0xDEAD_BEEF 0x20 0x0000_0000 RReg WReg;
+
+    TEST_READY { 0 1 RRRegField RoRRegField }
+    TEST_OVERRUN { 1 1 RRRegField W1cRegField }
+    TEST_ERRORS { 4 3 RRRegField W1cRegField }
+}
+
+reg! {
+    pub mod TEST_BLOCK TEST_SHARED;
+
+    0xDEAD_BEEF 0x20 0x0000_0001 RReg WReg AtomicReg;
+
+    TEST_ENABLE { 0 1 RRRegField WWRegField }
+}
+
+reg! {
+    #[endian = "big"]
+    pub mod TEST_BLOCK TEST_BIG_ENDIAN;
+
+    0xDEAD_BEEF 0x20 0x0000_0000 RReg WReg;
+
+    TEST_LOW { 0 8 RRRegField WWRegField }
+    TEST_HIGH { 24 8 RRRegField WWRegField }
+}
+
+reg! {
+    pub mod TEST_BLOCK TEST_COMMAND;
+
+    0xDEAD_BEEF 0x20 0x0000_0000 WReg WoReg;
+
+    TEST_START { 0 1 WWRegField WoWRegField }
+    TEST_CODE { 4 4 WWRegField WoWRegField }
+}
+
+reg! {
+    pub mod TEST_BLOCK TEST_CONTROL;
+
+    0xDEAD_BEEF 0x20 0x0000_0000 RReg WReg;
+
+    TEST_BUSY { 0 1 RRRegField RoRRegField }
+    TEST_START { 0 1 WWRegField WoWRegField }
+}
+
+reg! {
+    pub mod TEST_BLOCK TEST_BYTE;
+
+    0xDEAD_BEEF 0x08 0x80 RReg WReg;
+
+    TEST_LOW { 0 7 RRRegField WWRegField }
+    TEST_HIGH { 7 1 RRRegField WWRegField }
+}
+
+reg! {
+    pub mod TEST_BLOCK TEST_LOCK;
+
+    0x1000_0000 0x20 0x0000_0000 RReg WReg;
+
+    TEST_LOCKED { 0 1 RRRegField WWRegField }
+}
+
+reg! {
+    pub mod TEST_BLOCK TEST_TOGGLE;
+
+    0x1000_1000 0x20 0x0000_0000 RReg WReg;
+
+    TEST_FLAG { 0 1 RRRegField WWRegField }
 }
 
 reg::tokens! {
@@ -29,6 +143,7 @@ reg::tokens! {
     #[doc = "test block attribute"]
     pub mod TEST_BLOCK {
         TEST_REG;
+        TEST_SHARED;
     }
 }
 
@@ -38,6 +153,103 @@ reg_tokens! {
     pub struct Regs;
 }
 
+struct Logged<'a, R> {
+    name: &'static str,
+    log: &'a Mutex<Vec<&'static str>>,
+    _reg: R,
+}
+
+impl<R: Reg<Urt>> Reg<Urt> for Logged<'_, R> {
+    type CReg = R::CReg;
+    type SReg = R::SReg;
+    type UReg = R::UReg;
+    type Val = R::Val;
+
+    const ADDRESS: usize = R::ADDRESS;
+    const RESET: <R::Val as Bitfield>::Bits = R::RESET;
+
+    unsafe fn val_from(bits: <R::Val as Bitfield>::Bits) -> R::Val {
+        R::val_from(bits)
+    }
+}
+
+impl<R: Reg<Urt>> RReg<Urt> for Logged<'_, R> {
+    fn load_bits(&self) -> <R::Val as Bitfield>::Bits {
+        self.log.lock().unwrap().push(self.name);
+        R::RESET
+    }
+}
+
+struct Mapped<R> {
+    address: usize,
+    reg: R,
+}
+
+impl<T: RegTag, R: Reg<T>> Reg<T> for Mapped<R> {
+    type CReg = R::CReg;
+    type SReg = R::SReg;
+    type UReg = R::UReg;
+    type Val = R::Val;
+
+    const ADDRESS: usize = R::ADDRESS;
+    const RESET: <R::Val as Bitfield>::Bits = R::RESET;
+
+    unsafe fn val_from(bits: <R::Val as Bitfield>::Bits) -> R::Val {
+        R::val_from(bits)
+    }
+}
+
+impl<T: RegTag, R: Reg<T>> RReg<T> for Mapped<R> {
+    fn as_ptr(&self) -> *const <R::Val as Bitfield>::Bits {
+        self.address as *const _
+    }
+}
+
+impl<T: RegTag, R: Reg<T>> WReg<T> for Mapped<R> {
+    fn as_mut_ptr(&self) -> *mut <R::Val as Bitfield>::Bits {
+        self.address as *mut _
+    }
+}
+
+impl<'a> RegRef<'a, Urt> for Mapped<TestReg<Urt>> {
+    type Hold = test_block::test_reg::Hold<'a, Urt>;
+
+    fn hold(&'a self, val: Val) -> Self::Hold {
+        self.reg.hold(val)
+    }
+}
+
+impl<'a> RegHold<'a, Urt, Mapped<TestReg<Urt>>> for test_block::test_reg::Hold<'a, Urt> {
+    fn val(&self) -> Val {
+        RegHold::<Urt, TestReg<Urt>>::val(self)
+    }
+
+    fn set_val(&mut self, val: Val) {
+        RegHold::<Urt, TestReg<Urt>>::set_val(self, val);
+    }
+}
+
+/// Host simulation of the exclusive load/store loop provided by platform
+/// crates.
+fn fetch_update(ptr: *mut u32, f: impl FnOnce(u32) -> u32) -> u32 {
+    static EXCLUSIVE: AtomicBool = AtomicBool::new(false);
+    while EXCLUSIVE.swap(true, Ordering::Acquire) {}
+    let prev = unsafe { ptr::read_volatile(ptr) };
+    unsafe { ptr::write_volatile(ptr, f(prev)) };
+    EXCLUSIVE.store(false, Ordering::Release);
+    prev
+}
+
+#[no_mangle]
+extern "C" fn drone_reg_fetch_or_u32(ptr: *mut u32, bits: u32) -> u32 {
+    fetch_update(ptr, |prev| prev | bits)
+}
+
+#[no_mangle]
+extern "C" fn drone_reg_fetch_xor_u32(ptr: *mut u32, bits: u32) -> u32 {
+    fetch_update(ptr, |prev| prev ^ bits)
+}
+
 #[test]
 fn reg_default_val() {
     assert_eq!(unsafe { TestReg::<Srt>::take() }.default_val().bits(), 0xBEEF_CACE);
@@ -54,3 +266,317 @@ fn size_of_reg() {
 fn size_of_reg_val() {
     assert_eq!(size_of::<Val>(), 4);
 }
+
+#[test]
+fn write_field_preserves_bits() {
+    let mut mem = 0xBEEF_CAC0_u32;
+    let mut reg =
+        Mapped { address: &mut mem as *mut u32 as usize, reg: unsafe { TestReg::<Urt>::take() } };
+    reg.modify(|r| r.write_test_bits(0b101));
+    assert_eq!(reg.load().test_bits(), 0b101);
+    assert!(!reg.load().test_bit());
+    drop(reg);
+    assert_eq!(mem, 0xBEEF_CACA);
+}
+
+#[test]
+fn field_enum() {
+    let reg = unsafe { TestReg::<Urt>::take() };
+    for (bits, mode) in [(0, Mode::Off), (1, Mode::Low), (2, Mode::High), (3, Mode::Max)].iter() {
+        let mut hold = reg.hold(unsafe { TestReg::<Urt>::val_from(0xFFFF_FFCF | (bits << 4)) });
+        assert_eq!(hold.read_test_mode(), *mode);
+        assert_eq!(hold.test_mode(), *bits);
+        hold.write_test_mode(Mode::Off);
+        assert_eq!(hold.val().bits(), 0xFFFF_FFCF);
+        hold.write_test_mode(*mode);
+        assert_eq!(hold.val().bits(), 0xFFFF_FFCF | (bits << 4));
+    }
+}
+
+#[test]
+fn val_from_bits_checked() {
+    let reg = unsafe { TestReg::<Urt>::take() };
+    assert_eq!(reg.from_bits_checked(0b11_1010).unwrap().bits(), 0b11_1010);
+    let err = reg.from_bits_checked((1 << 31) | 1).err().unwrap();
+    assert_eq!(err.reserved_bits(), 1 << 31);
+}
+
+#[test]
+fn dyn_reg() {
+    let mut mem = [0xAAAA_AAAA_u32, 0x5555_5555];
+    let base0 = &mut mem[0] as *mut u32 as usize;
+    let base1 = &mut mem[1] as *mut u32 as usize;
+    let mut reg0 = unsafe { DynReg::from_base(TestReg::<Urt>::take(), base0) };
+    let mut reg1 = unsafe { DynReg::from_base(TestReg::<Urt>::take(), base1) };
+    assert_eq!(reg0.load_val().bits(), 0xAAAA_AAAA);
+    assert_eq!(reg1.load_val().bits(), 0x5555_5555);
+    reg0.store_val(reg0.reg().default_val());
+    reg1.store_val(unsafe { TestReg::<Urt>::val_from(0x1234_5678) });
+    assert_eq!(reg0.load_val().bits(), 0xBEEF_CACE);
+    assert_eq!(reg1.load_val().bits(), 0x1234_5678);
+    drop((reg0, reg1));
+    assert_eq!(mem, [0xBEEF_CACE, 0x1234_5678]);
+}
+
+#[test]
+fn dyn_reg_reset() {
+    let mut mem = 0_u32;
+    let mut reg =
+        unsafe { DynReg::from_base(TestReg::<Urt>::take(), &mut mem as *mut u32 as usize) };
+    reg.store_val(unsafe { TestReg::<Urt>::val_from(0x1234_5678) });
+    assert_eq!(reg.load_val().bits(), 0x1234_5678);
+    reg.reset();
+    assert_eq!(reg.load_val().bits(), 0xBEEF_CACE);
+}
+
+#[test]
+fn write_only_reg() {
+    let mut mem = 0xFFFF_FFFF_u32;
+    let mut reg = unsafe {
+        DynReg::from_base(
+            test_block_test_command::Reg::<Urt>::take(),
+            &mut mem as *mut u32 as usize,
+        )
+    };
+    let val =
+        reg.reg().hold(reg.reg().default_val()).set_test_start().write_test_code(0b1010).val();
+    reg.store_val(val);
+    drop(reg);
+    assert_eq!(mem, 0b1010_0001);
+}
+
+#[test]
+fn aliased_fields() {
+    let mut mem = 0_u32;
+    let mut reg = unsafe {
+        DynReg::from_base(
+            test_block_test_control::Reg::<Urt>::take(),
+            &mut mem as *mut u32 as usize,
+        )
+    };
+    assert!(!reg.reg().hold(reg.load_val()).test_busy());
+    let val = reg.reg().hold(reg.reg().default_val()).set_test_start().val();
+    reg.store_val(val);
+    assert!(reg.reg().hold(reg.load_val()).test_busy());
+    drop(reg);
+    assert_eq!(mem, 1);
+}
+
+#[test]
+fn byte_reg() {
+    assert_eq!(size_of::<test_block_test_byte::Val>(), 1);
+    let mut mem = 0_u8;
+    let mut reg = unsafe {
+        DynReg::from_base(test_block_test_byte::Reg::<Urt>::take(), &mut mem as *mut u8 as usize)
+    };
+    reg.reset();
+    assert!(reg.reg().hold(reg.load_val()).test_high());
+    let val = reg.reg().hold(reg.load_val()).clear_test_high().write_test_low(0x7F).val();
+    reg.store_val(val);
+    drop(reg);
+    assert_eq!(mem, 0x7F);
+}
+
+#[test]
+fn val_u8_round_trip() {
+    let reg = unsafe { test_block_test_byte::Reg::<Urt>::take() };
+    let val = reg.val_from_bits(0xA5);
+    assert_eq!(u8::from(val), 0xA5);
+    assert_eq!(reg.val_from_bits(u8::from(val)).bits(), 0xA5);
+    assert_eq!(reg.try_val_from_bits(0xFF).unwrap().bits(), 0xFF);
+}
+
+#[test]
+fn w1c_field_clear_val() {
+    let status = unsafe { test_block_test_status::Reg::<Urt>::take() };
+    assert_eq!(status.test_overrun.clear_val().bits(), 0b0000_0010);
+    assert_eq!(status.test_errors.clear_val().bits(), 0b0111_0000);
+}
+
+#[test]
+fn sync_unsync_round_trip() {
+    let reg = unsafe { TestReg::<Srt>::take() };
+    let reg: TestReg<Urt> = reg.into_unsync();
+    assert_eq!(reg.default().set_test_bit().val().bits(), 0xBEEF_CACF);
+    let reg: TestReg<Srt> = reg.into_sync();
+    assert_eq!(reg.default().set_test_bit().val().bits(), 0xBEEF_CACF);
+}
+
+#[test]
+fn address_and_reset_value() {
+    assert_eq!(TestReg::<Urt>::ADDRESS, 0xDEAD_BEEF);
+    assert_eq!(TestReg::<Urt>::RESET, 0xBEEF_CACE);
+}
+
+#[test]
+fn bitband_alias() {
+    assert_eq!(reg::bitband_alias(0x4001_080C, 3), 0x4221_018C);
+    assert_eq!(reg::bitband_alias(0x4000_0000, 0), 0x4200_0000);
+    assert_eq!(reg::bitband_alias(0x2000_0004, 31), 0x2200_00FC);
+}
+
+#[test]
+fn field_mask_and_offset() {
+    assert_eq!(test_block::test_reg::TEST_BIT_MASK, 0b1);
+    assert_eq!(test_block::test_reg::TEST_BIT_OFFSET, 0);
+    assert_eq!(test_block::test_reg::TEST_BITS_MASK, 0b111 << 1);
+    assert_eq!(test_block::test_reg::TEST_BITS_OFFSET, 1);
+}
+
+#[test]
+fn hold_multiple_fields() {
+    let mut mem = 0xFFFF_0000_u32;
+    let mut reg =
+        unsafe { DynReg::from_base(TestReg::<Urt>::take(), &mut mem as *mut u32 as usize) };
+    let val = reg.reg().hold(reg.load_val()).set_test_bit().write_test_bits(0b101).val();
+    reg.store_val(val);
+    drop(reg);
+    assert_eq!(mem, 0xFFFF_0000 | 0b1011);
+}
+
+#[test]
+fn val_bit_ops() {
+    let a = unsafe { TestReg::<Urt>::val_from(0xBEEF_CACE) };
+    let b = unsafe { TestReg::<Urt>::val_from(0x1234_5678) };
+    assert_eq!((a | b).bits(), 0xBEEF_CACE | 0x1234_5678);
+    assert_eq!((a & b).bits(), 0xBEEF_CACE & 0x1234_5678);
+    assert_eq!((a ^ b).bits(), 0xBEEF_CACE ^ 0x1234_5678);
+    assert_eq!((!a).bits(), 0x4110_3531);
+}
+
+#[test]
+fn val_u32_round_trip() {
+    let reg = unsafe { TestReg::<Urt>::take() };
+    let val = reg.try_val_from_bits(0b11_1010).unwrap();
+    assert_eq!(u32::from(val), 0b11_1010);
+    assert_eq!(reg.try_val_from_bits(u32::from(val)).unwrap().bits(), 0b11_1010);
+    assert_eq!(reg.try_val_from_bits(1 << 31).err().unwrap().reserved_bits(), 1 << 31);
+}
+
+#[test]
+fn atomic_reg_copy() {
+    let reg = unsafe { test_block::TestShared::<Crt>::take() };
+    let copy = reg;
+    assert!(reg.default().test_enable());
+    assert_eq!(copy.default_val().bits(), 1);
+}
+
+#[test]
+fn take_all_once() {
+    let regs = Regs::take_all().unwrap();
+    let (test_reg, test_shared) = regs.split();
+    assert_eq!(test_reg.default_val().bits(), 0xBEEF_CACE);
+    drop(test_shared);
+    assert!(Regs::take_all().is_err());
+}
+
+#[test]
+fn val_raw_handoff() {
+    let stash = AtomicU32::new(0);
+    let mut val = unsafe { TestReg::<Srt>::take() }.default_val();
+    *val.bits_mut() = 0x1234_5678;
+    stash.store(val.bits(), Ordering::Release);
+    let restored = unsafe { Val::from_raw(stash.load(Ordering::Acquire)) };
+    assert_eq!(restored.bits(), 0x1234_5678);
+    assert_eq!(restored.bits(), val.bits());
+}
+
+#[test]
+fn val_debug() {
+    assert_eq!(
+        format!("{:?}", unsafe { Val::from_raw(0x2B) }),
+        "TEST_REG { TEST_BIT: 1, TEST_BITS: 5, TEST_MODE: 2 }"
+    );
+    assert_eq!(
+        format!("{:?}", unsafe { Val::from_raw(0x12B) }),
+        "TEST_REG { TEST_BIT: 1, TEST_BITS: 5, TEST_MODE: 2, reserved: 0x100 }"
+    );
+}
+
+#[test]
+fn val_fields() {
+    match unsafe { Val::from_raw(0x2B) }.fields() {
+        test_block::test_reg::Fields { test_bit: true, test_bits: 5, test_mode: 2 } => {}
+        fields => panic!("unexpected fields: {:?}", fields),
+    }
+}
+
+#[test]
+fn toggle_bit() {
+    let mut mem = 0b1000_u32;
+    let mut reg =
+        Mapped { address: &mut mem as *mut u32 as usize, reg: unsafe { TestReg::<Urt>::take() } };
+    reg.modify(|r| r.toggle_test_bit());
+    assert!(reg.load().test_bit());
+    reg.modify(|r| r.toggle_test_bit());
+    assert!(!reg.load().test_bit());
+    drop(reg);
+    assert_eq!(mem, 0b1000);
+    let reg = Mapped {
+        address: &mut mem as *mut u32 as usize,
+        reg: unsafe { test_block_test_toggle::Reg::<Crt>::take() },
+    };
+    reg.toggle_bit(0);
+    assert_eq!(unsafe { ptr::read_volatile(&mem) }, 0b1001);
+    reg.toggle_bit(0);
+    drop(reg);
+    assert_eq!(mem, 0b1000);
+    let _: fn(&mut test_block_test_toggle::Reg<Urt>) =
+        test_block_test_toggle::Reg::<Urt>::toggle_test_flag;
+    let _: fn(&test_block_test_toggle::Reg<Srt>) =
+        test_block_test_toggle::Reg::<Srt>::toggle_test_flag;
+    let _: fn(&test_block_test_toggle::Reg<Crt>) =
+        test_block_test_toggle::Reg::<Crt>::toggle_test_flag;
+}
+
+#[test]
+fn big_endian_reg() {
+    let mut mem = u32::from_ne_bytes([0x12, 0x00, 0x00, 0x34]);
+    let mut reg = unsafe {
+        DynReg::from_base(
+            test_block_test_big_endian::Reg::<Urt>::take(),
+            &mut mem as *mut u32 as usize,
+        )
+    };
+    let val = reg.load_val();
+    assert_eq!(reg.reg().hold(val).test_low(), 0x34);
+    assert_eq!(reg.reg().hold(val).test_high(), 0x12);
+    let written = reg.reg().hold(val).write_test_low(0x56).val();
+    reg.store_val(written);
+    drop(reg);
+    assert_eq!(mem.to_ne_bytes(), [0x12, 0x00, 0x00, 0x56]);
+}
+
+#[test]
+fn read_group_order() {
+    let log = Mutex::new(Vec::new());
+    let status = Logged {
+        name: "status",
+        log: &log,
+        _reg: unsafe { test_block_test_status::Reg::<Urt>::take() },
+    };
+    let data = Logged { name: "data", log: &log, _reg: unsafe { TestReg::<Urt>::take() } };
+    let (status_val, data_val) = reg::read_group!(status, data);
+    assert_eq!(status_val.bits(), 0);
+    assert_eq!(data_val.bits(), 0xBEEF_CACE);
+    assert_eq!(*log.lock().unwrap(), ["status", "data"]);
+    let (data_val, _) = reg::read_group!(data, status);
+    assert_eq!(data_val.bits(), 0xBEEF_CACE);
+    assert_eq!(*log.lock().unwrap(), ["status", "data", "data", "status"]);
+}
+
+#[test]
+fn test_and_set_bit() {
+    let mut mem = 0b1000_u32;
+    let reg = Mapped {
+        address: &mut mem as *mut u32 as usize,
+        reg: unsafe { test_block_test_lock::Reg::<Crt>::take() },
+    };
+    assert!(!reg.test_and_set_bit(0));
+    assert!(reg.test_and_set_bit(0));
+    assert!(reg.test_and_set_bit(3));
+    drop(reg);
+    assert_eq!(mem, 0b1001);
+    let _: fn(&test_block_test_lock::Reg<Crt>) -> bool =
+        test_block_test_lock::Reg::<Crt>::test_and_set_test_locked;
+}
@@ -21,6 +21,7 @@ thr! {
 
     /// Test doc attribute
     #[doc = "test attribute"]
+    #[derive(Debug)]
     pub struct Thr {
         #[allow(dead_code)]
         pub bar: isize = 1 - 2;
@@ -125,3 +126,27 @@ fn fiber_once() {
         assert_eq!(counter.load(Relaxed), -2);
     }
 }
+
+#[test]
+fn thread_debug() {
+    let thr = Thr::new(0);
+    thr.fib_chain().add(fib::new_once(|| {}));
+    assert_eq!(format!("{:?}", thr), "Thr { fib_chain_is_empty: false, bar: -1 }");
+}
+
+#[test]
+fn thread_add_fib() {
+    let counter = Arc::new(AtomicI8::new(0));
+    let inner = Arc::clone(&counter);
+    let thr = Thr::new(0);
+    thr.add_fib(fib::new_once(move || {
+        inner.fetch_add(1, Relaxed);
+    }));
+    assert_eq!(unsafe { thr.fib_chain().drain_once() }, 0);
+    assert_eq!(counter.load(Relaxed), 1);
+}
+
+#[test]
+fn thread_name() {
+    assert_eq!(Thr::NAME, "Thr");
+}
@@ -0,0 +1,28 @@
+#![feature(const_fn)]
+
+use drone_core::thr;
+use std::sync::atomic::Ordering::*;
+
+static mut THREADS: [Thr; 2] = [Thr::new(0), Thr::new(1)];
+
+thr! {
+    use THREADS;
+
+    pub struct Thr {
+        #[atomic]
+        pub counter: u32 = index as u32;
+    }
+
+    pub struct ThrLocal {}
+}
+
+#[test]
+fn atomic_counter() {
+    let thr = unsafe { &THREADS[1] };
+    assert_eq!(thr.load_counter(Relaxed), 1);
+    assert_eq!(thr.fetch_add_counter(2, Relaxed), 1);
+    assert_eq!(thr.load_counter(Relaxed), 3);
+    thr.store_counter(10, Relaxed);
+    assert_eq!(thr.counter.load(Relaxed), 10);
+    assert_eq!(unsafe { THREADS[0].load_counter(Relaxed) }, 0);
+}
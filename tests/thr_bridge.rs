@@ -0,0 +1,44 @@
+#![feature(const_fn)]
+#![feature(generators)]
+
+use drone_core::{
+    fib::{self, Chain},
+    thr,
+};
+use std::sync::atomic::{AtomicUsize, Ordering::*};
+
+static mut THREADS: [Thr; 2] = [Thr::new(0), Thr::new(1)];
+
+static PENDS: [AtomicUsize; 2] = [AtomicUsize::new(0), AtomicUsize::new(0)];
+
+thr! {
+    use THREADS;
+
+    swi: u8 = 0;
+
+    pub struct Thr {}
+
+    pub struct ThrLocal {}
+}
+
+#[no_mangle]
+extern "C" fn drone_thr_pend_swi(swi: u8) {
+    PENDS[usize::from(swi)].fetch_add(1, SeqCst);
+}
+
+#[test]
+fn bridge_pends_target() {
+    let b = unsafe { &THREADS[1] };
+    let chain = Chain::new();
+    chain.add(fib::new_bridge(b, || {
+        yield;
+        yield;
+    }));
+    assert_eq!(unsafe { chain.drain_once() }, 1);
+    assert_eq!(PENDS[1].load(SeqCst), 1);
+    assert_eq!(unsafe { chain.drain_once() }, 1);
+    assert_eq!(PENDS[1].load(SeqCst), 2);
+    assert_eq!(unsafe { chain.drain_once() }, 0);
+    assert_eq!(PENDS[1].load(SeqCst), 2);
+    assert_eq!(PENDS[0].load(SeqCst), 0);
+}
@@ -0,0 +1,42 @@
+#![feature(const_fn)]
+
+use drone_core::{thr, thr::Thread};
+
+pub struct RingBuf {
+    buf: [u8; 32],
+    len: usize,
+}
+
+impl RingBuf {
+    pub const fn new(len: usize) -> Self {
+        Self { buf: [0; 32], len }
+    }
+}
+
+static mut THREADS: [Thr; 2] = [Thr::new(0), Thr::new(1)];
+
+thr! {
+    use THREADS;
+
+    pub struct Thr {
+        pub ring: RingBuf = RingBuf::new(16);
+        pub table: [usize; 4] = [index; 4];
+    }
+
+    pub struct ThrLocal {
+        pub scratch: RingBuf = RingBuf::new(index * 8);
+    }
+}
+
+static THR: Thr = Thr::new(3);
+
+#[test]
+fn const_fn_initializers() {
+    let thr = unsafe { &THREADS[1] };
+    assert_eq!(thr.ring.len, 16);
+    assert_eq!(thr.ring.buf.len(), 32);
+    assert_eq!(thr.table, [1; 4]);
+    assert_eq!(unsafe { THREADS[0].table }, [0; 4]);
+    assert_eq!(THR.table, [3; 4]);
+    assert_eq!(unsafe { thr.local() }.scratch.len, 8);
+}
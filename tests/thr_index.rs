@@ -0,0 +1,29 @@
+#![feature(const_fn)]
+
+use drone_core::thr::{self, ThreadArray};
+use std::collections::HashSet;
+
+static mut THREADS: [Thr; 3] = [Thr::new(0), Thr::new(1), Thr::new(2)];
+
+thr! {
+    use THREADS;
+    pub struct Thr {
+        pub id: usize = index * 2;
+    }
+    pub struct ThrLocal {}
+}
+
+#[test]
+fn distinct_ids() {
+    let ids = unsafe { THREADS.iter().map(|thr| thr.id).collect::<Vec<_>>() };
+    assert_eq!(ids, [0, 2, 4]);
+    assert_eq!(ids.iter().collect::<HashSet<_>>().len(), 3);
+}
+
+#[test]
+fn all_threads() {
+    let addrs = Thr::all().map(|thr| thr as *const Thr).collect::<HashSet<_>>();
+    assert_eq!(Thr::all().count(), 3);
+    assert_eq!(addrs.len(), 3);
+    assert_eq!(Thr::all().map(|thr| thr.id).collect::<Vec<_>>(), [0, 2, 4]);
+}
@@ -0,0 +1,50 @@
+#![feature(const_fn)]
+#![feature(generators)]
+
+use drone_core::{
+    thr::{self, prelude::*, Thread},
+    token::Token,
+};
+
+static mut THREADS: [Thr; 1] = [Thr::new(0)];
+
+thr! {
+    use THREADS;
+    pub struct Thr {}
+    pub struct ThrLocal {
+        counter: u32 = 0;
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Thr0;
+
+unsafe impl Token for Thr0 {
+    unsafe fn take() -> Self {
+        Self
+    }
+}
+
+unsafe impl ThrToken for Thr0 {
+    type Thr = Thr;
+
+    const THR_NUM: usize = 0;
+}
+
+#[test]
+fn local_counter() {
+    unsafe {
+        let thr = Thr0::take();
+        thr.add(|| {
+            loop {
+                *thr::local::<Thr>().counter_mut() += 1;
+                yield;
+            }
+        });
+        assert_eq!(*thr::local::<Thr>().counter(), 0);
+        thr.to_thr().fib_chain().drain();
+        assert_eq!(*thr::local::<Thr>().counter(), 1);
+        thr.to_thr().fib_chain().drain();
+        assert_eq!(*thr::local::<Thr>().counter(), 2);
+    }
+}
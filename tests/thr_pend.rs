@@ -0,0 +1,62 @@
+#![feature(const_fn)]
+
+use drone_core::thr::Pend;
+use std::sync::atomic::{AtomicUsize, Ordering::*};
+
+static PENDS: [AtomicUsize; 7] = [
+    AtomicUsize::new(0),
+    AtomicUsize::new(0),
+    AtomicUsize::new(0),
+    AtomicUsize::new(0),
+    AtomicUsize::new(0),
+    AtomicUsize::new(0),
+    AtomicUsize::new(0),
+];
+
+mod a {
+    use drone_core::thr;
+
+    pub static mut THREADS: [Thr; 1] = [Thr::new(0)];
+
+    thr! {
+        use THREADS;
+        swi: u8 = 4;
+        pub struct Thr {}
+        pub struct ThrLocal {}
+    }
+}
+
+mod b {
+    use drone_core::thr;
+
+    pub static mut THREADS: [Thr; 2] = [Thr::new(0), Thr::new(1)];
+
+    thr! {
+        use THREADS;
+        swi: u8 = 5;
+        pub struct Thr {}
+        pub struct ThrLocal {}
+    }
+}
+
+#[no_mangle]
+extern "C" fn drone_thr_pend_swi(swi: u8) {
+    PENDS[usize::from(swi)].fetch_add(1, SeqCst);
+}
+
+#[test]
+fn pend_dyn() {
+    let threads: [&dyn Pend; 3] = unsafe { [&a::THREADS[0], &b::THREADS[0], &b::THREADS[1]] };
+    for thr in &threads {
+        thr.pend();
+    }
+    threads[2].pend();
+    let pends = PENDS.iter().map(|pends| pends.load(SeqCst)).collect::<Vec<_>>();
+    assert_eq!(pends, [0, 0, 0, 0, 1, 1, 2]);
+}
+
+#[test]
+fn swi() {
+    assert_eq!(a::Thr::SWI, 4);
+    assert_eq!(b::Thr::SWI, 5);
+}
@@ -0,0 +1,70 @@
+#![feature(const_fn)]
+
+use drone_core::{
+    thr::{self, prelude::*, thread_resume, Thread, ThreadLocal},
+    token::Token,
+};
+use std::sync::{Arc, Mutex};
+
+static mut THREADS: [Thr; 3] = [Thr::new(0), Thr::new(1), Thr::new(2)];
+
+thr! {
+    use THREADS;
+    pub struct Thr {}
+    pub struct ThrLocal {}
+}
+
+macro_rules! thr_num {
+    ($name:ident, $position:expr) => {
+        #[derive(Clone, Copy)]
+        struct $name;
+
+        unsafe impl Token for $name {
+            unsafe fn take() -> Self {
+                Self
+            }
+        }
+
+        unsafe impl ThrToken for $name {
+            type Thr = Thr;
+
+            const THR_NUM: usize = $position;
+        }
+    };
+}
+
+thr_num!(Thr0, 0);
+thr_num!(Thr1, 1);
+thr_num!(Thr2, 2);
+
+fn depth() -> usize {
+    thr::local::<Thr>().preempted().depth()
+}
+
+#[test]
+fn nested_depth() {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    unsafe {
+        let log0 = Arc::clone(&log);
+        Thr0::take().add_once(move || {
+            log0.lock().unwrap().push(depth());
+            thread_resume::<Thr1>();
+            log0.lock().unwrap().push(depth());
+        });
+        let log1 = Arc::clone(&log);
+        Thr1::take().add_once(move || {
+            log1.lock().unwrap().push(depth());
+            thread_resume::<Thr2>();
+            log1.lock().unwrap().push(depth());
+        });
+        let log2 = Arc::clone(&log);
+        Thr2::take().add_once(move || {
+            log2.lock().unwrap().push(depth());
+        });
+        thread_resume::<Thr0>();
+        for thr in &THREADS {
+            assert_eq!(thr.local().preempted().depth(), 0);
+        }
+    }
+    assert_eq!(*log.lock().unwrap(), [1, 2, 3, 2, 1]);
+}
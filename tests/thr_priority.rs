@@ -0,0 +1,48 @@
+#![feature(const_fn)]
+
+use drone_core::thr::{Thread, BASE_PRIORITY};
+
+mod high {
+    use drone_core::thr;
+
+    pub static mut THREADS: [Thr; 1] = [Thr::new(0)];
+
+    thr! {
+        use THREADS;
+        priority: u8 = 2;
+        pub struct Thr {}
+        pub struct ThrLocal {}
+    }
+}
+
+mod low {
+    use drone_core::thr;
+
+    pub static mut THREADS: [Thr; 1] = [Thr::new(0)];
+
+    thr! {
+        use THREADS;
+        priority: u8 = 1;
+        pub struct Thr {}
+        pub struct ThrLocal {}
+    }
+}
+
+mod base {
+    use drone_core::thr;
+
+    pub static mut THREADS: [Thr; 1] = [Thr::new(0)];
+
+    thr! {
+        use THREADS;
+        pub struct Thr {}
+        pub struct ThrLocal {}
+    }
+}
+
+#[test]
+fn priority() {
+    assert_eq!(high::Thr::PRIORITY, 2);
+    assert_eq!(low::Thr::PRIORITY, 1);
+    assert_eq!(base::Thr::PRIORITY, BASE_PRIORITY);
+}
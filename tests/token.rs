@@ -0,0 +1,38 @@
+use drone_core::token::{simple_token, AlreadyTakenError, CheckedToken};
+
+simple_token! {
+    struct FooToken;
+}
+
+simple_token! {
+    struct BarToken;
+}
+
+simple_token! {
+    struct BazToken;
+}
+
+#[test]
+fn try_take_twice() {
+    let foo = FooToken::try_take().unwrap();
+    assert_eq!(FooToken::try_take().err(), Some(AlreadyTakenError));
+    assert_eq!(FooToken::try_take().err(), Some(AlreadyTakenError));
+    drop(foo);
+}
+
+#[test]
+fn release() {
+    let bar = BarToken::try_take().unwrap();
+    bar.release();
+    let bar = BarToken::try_take().unwrap();
+    assert!(BarToken::try_take().is_err());
+    bar.release();
+}
+
+#[test]
+fn drop_without_release() {
+    let baz = BazToken::try_take().unwrap();
+    assert!(BazToken::try_take().is_err());
+    drop(baz);
+    assert!(BazToken::try_take().is_err());
+}